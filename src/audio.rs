@@ -0,0 +1,186 @@
+//! 有声小说下载
+
+use crate::{Album, Id, Proxy, Track};
+
+use anyhow::Result;
+use id3::{
+    frame::{Picture, PictureType},
+    Tag, TagLike, Version,
+};
+use serde::Serialize;
+use std::{collections::HashSet, fs, path::PathBuf};
+
+/// 已保存的音频
+#[derive(Debug, Serialize)]
+pub struct SavedTrack {
+    pub id: Id,        //<! 音频 ID
+    pub title: String, //<! 标题
+    pub path: PathBuf, //<! 保存路径
+}
+
+/// 未能保存的音频
+#[derive(Debug, Serialize)]
+pub struct SkippedTrack {
+    pub id: Id,         //<! 音频 ID
+    pub title: String,  //<! 标题
+    pub reason: String, //<! 原因，未解锁或下载失败的错误
+}
+
+/// 一次下载的结果
+#[derive(Debug, Default, Serialize)]
+pub struct AlbumDownload {
+    pub saved: Vec<SavedTrack>,     //<! 新保存的音频
+    pub skipped: Vec<SkippedTrack>, //<! 未解锁或下载失败的音频
+}
+
+pub struct AlbumDownloader<'a> {
+    proxy: &'a Proxy,
+    tagged: bool, //<! 是否为 MP3 文件写入 ID3 标签
+}
+
+/// 替换文件名中不允许出现的字符
+fn file_name_of(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn mime_type_of(image: &[u8]) -> &'static str {
+    match image {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => "image/jpeg",
+    }
+}
+
+/// 由音频地址推断扩展名，无法推断时视为 MP3
+fn extension_of(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    match path.rsplit_once('/').map_or(path, |e| e.1).rsplit_once('.') {
+        Some((_, ext)) if !ext.is_empty() && ext.len() <= 4 => ext.to_lowercase(),
+        _ => "mp3".to_string(),
+    }
+}
+
+impl<'a> AlbumDownloader<'a> {
+    pub fn new(proxy: &'a Proxy) -> Self {
+        Self {
+            proxy,
+            tagged: true,
+        }
+    }
+
+    pub fn with_tags(mut self, tagged: bool) -> Self {
+        self.tagged = tagged;
+        self
+    }
+
+    /// 获取有声小说的详情与音频列表
+    pub fn album(&self, album_id: Id) -> Result<Album> {
+        let tracks = self.proxy.album_chapters_of(album_id)?;
+        self.proxy.album_info(album_id)?.parse(tracks)
+    }
+
+    fn tag(&self, album: &Album, track: &Track, number: usize, cover: Option<&[u8]>) -> Tag {
+        let mut tag = Tag::new();
+        tag.set_title(&track.title);
+        tag.set_album(&album.name);
+        if let Some(author) = &album.author {
+            tag.set_artist(author);
+            tag.set_album_artist(author);
+        }
+        tag.set_track(number as u32);
+        tag.set_total_tracks(album.tracks.len() as u32);
+        tag.set_genre("Audiobook");
+        if let Some(cover) = cover {
+            tag.add_frame(Picture {
+                mime_type: mime_type_of(cover).to_string(),
+                picture_type: PictureType::CoverFront,
+                description: String::new(),
+                data: cover.to_vec(),
+            });
+        }
+        tag
+    }
+
+    /// 下载单个音频，未解锁时返回 `None`
+    fn download_track(
+        &self,
+        album: &Album,
+        track: &Track,
+        number: usize,
+        path: PathBuf,
+        cover: Option<&[u8]>,
+    ) -> Result<Option<PathBuf>> {
+        let Some(url) = self.proxy.album_chapter(album.id, track.id)?.parse()?.url else {
+            return Ok(None);
+        };
+        let data = reqwest::blocking::get(&url)?.error_for_status()?.bytes()?;
+        let ext = extension_of(&url);
+        let path = path.with_extension(&ext);
+        fs::write(&path, &data)?;
+        if self.tagged && ext == "mp3" {
+            self.tag(album, track, number, cover)
+                .write_to_path(&path, Version::Id3v24)?;
+        }
+        Ok(Some(path))
+    }
+
+    /// 下载音频到 `dir` 下以专辑名命名的目录，文件名以序号开头以保持顺序
+    ///
+    /// 已存在的文件被跳过；未解锁或下载失败的音频记录在 `skipped` 中，不影响其它音频的下载
+    pub fn download(&self, album: &Album, dir: PathBuf) -> Result<AlbumDownload> {
+        let dir = dir.join(file_name_of(&album.name));
+        fs::create_dir_all(&dir)?;
+        let cover = match self.tagged && !album.cover.is_empty() {
+            true => reqwest::blocking::get(&album.cover)
+                .and_then(|e| e.bytes())
+                .ok(),
+            false => None,
+        };
+
+        let existing: HashSet<String> = fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.rsplit_once('.').map(|e| e.0.to_string())
+            })
+            .collect();
+
+        let width = album.tracks.len().to_string().len().max(2);
+        let mut result = AlbumDownload::default();
+        for (index, track) in album.tracks.iter().enumerate() {
+            let number = index + 1;
+            let prefix = format!("{number:0width$} {}", file_name_of(&track.title));
+            if existing.contains(&prefix) {
+                continue;
+            }
+
+            // 标题中可能含有 `.`，先以占位的扩展名构造路径，再替换为实际的扩展名
+            let path = dir.join(format!("{prefix}.mp3"));
+            let reason = match self.download_track(album, track, number, path, cover.as_deref()) {
+                Ok(Some(path)) => {
+                    result.saved.push(SavedTrack {
+                        id: track.id,
+                        title: track.title.to_owned(),
+                        path,
+                    });
+                    continue;
+                }
+                Ok(None) => "locked".to_string(),
+                Err(err) => err.to_string(),
+            };
+            result.skipped.push(SkippedTrack {
+                id: track.id,
+                title: track.title.to_owned(),
+                reason,
+            });
+        }
+        Ok(result)
+    }
+}
@@ -1,5 +1,215 @@
-use crate::Proxy;
+use super::types;
+use crate::{request::*, Proxy, Value};
+
+use anyhow::{bail, Result};
+use reqwest::blocking::Response;
+use serde_json::json;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ActionError {
+    InvalidAmount,
+    NoTicketsLeft,
+    InsufficientTickets { available: usize, required: usize },
+    InsufficientBalance { available: usize, required: usize },
+    ExceedsLimit { limit: usize, required: usize },
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAmount => write!(f, "amount must be positive"),
+            Self::NoTicketsLeft => write!(f, "no monthly tickets left"),
+            Self::InsufficientTickets {
+                available,
+                required,
+            } => write!(
+                f,
+                "insufficient monthly tickets: {available} available, {required} required"
+            ),
+            Self::InsufficientBalance {
+                available,
+                required,
+            } => write!(
+                f,
+                "insufficient balance: {available} available, {required} required"
+            ),
+            Self::ExceedsLimit { limit, required } => {
+                write!(f, "{required} exceeds the spending limit {limit}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl ActionError {
+    /// 检查月票投票是否可行
+    pub fn check_vote(available: usize, required: usize, limit: usize) -> Result<(), Self> {
+        if required == 0 {
+            Err(Self::InvalidAmount)
+        } else if required > limit {
+            Err(Self::ExceedsLimit { limit, required })
+        } else if available == 0 {
+            Err(Self::NoTicketsLeft)
+        } else if required > available {
+            Err(Self::InsufficientTickets {
+                available,
+                required,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 检查打赏是否可行
+    pub fn check_bonus(available: usize, required: usize, limit: usize) -> Result<(), Self> {
+        if required == 0 {
+            Err(Self::InvalidAmount)
+        } else if required > limit {
+            Err(Self::ExceedsLimit { limit, required })
+        } else if required > available {
+            Err(Self::InsufficientBalance {
+                available,
+                required,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// 解析未经抓包验证的接口的响应，返回 `data` 字段
+///
+/// 响应不是 `{ "status": { "httpCode": ... }, "data": ... }` 的格式时返回错误，
+/// 避免把推测错误的接口的响应当作成功
+fn unpack_unverified(status_code: u16, text: &str) -> Result<Value> {
+    let body: Value = serde_json::from_str(text)
+        .map_err(|_| anyhow::anyhow!("unexpected response from an unverified endpoint: {text}"))?;
+    let Some(status) = body
+        .get("status")
+        .and_then(|e| serde_json::from_value::<types::Status>(e.clone()).ok())
+    else {
+        bail!("unexpected response from an unverified endpoint: {text}");
+    };
+    if status_code != 200 || status.httpCode != 200 {
+        match status.msg {
+            Some(msg) => bail!(msg),
+            None => bail!("request failed with {}", status.httpCode),
+        }
+    }
+    match body.get("data") {
+        Some(data) => Ok(data.clone()),
+        None => bail!("unexpected response from an unverified endpoint: {text}"),
+    }
+}
+
+#[inline]
+fn unpack_unverified_resp(resp: Response) -> Result<Value> {
+    let status_code = resp.status().as_u16();
+    unpack_unverified(status_code, &resp.text()?)
+}
 
 impl Proxy {
+    /// 使用火卷（或代金卷）购买小说章节
+    ///
+    /// 未经抓包验证，接口与请求体 `{ "orderType": "readOrder", "chapIds": [...] }` 为推测，
+    /// 响应格式不符时返回错误
+    pub fn order_chapters(&self, novel_id: i32, chapter_ids: &[i32]) -> Result<Value> {
+        let order = json!({
+            "orderType": "readOrder",
+            "orderAll": false,
+            "autoOrder": false,
+            "chapIds": chapter_ids,
+        });
+        let resp = self
+            .request(Method::POST, &format!("/novels/{novel_id}/orderedchaps"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(order.to_string())
+            .send()?;
+        unpack_unverified_resp(resp)
+    }
+
+    /// 获取当前用户剩余的月票
+    ///
+    /// 未经抓包验证，接口与返回格式为推测，返回格式不符时返回错误
+    pub fn ticket_info(&self) -> Result<types::TicketInfo> {
+        let data = unpack_unverified_resp(self.request(Method::GET, "/user/ticketInfo").send()?)?;
+        serde_json::from_value(data.clone()).map_err(|_| {
+            anyhow::anyhow!("unexpected ticket info from an unverified endpoint: {data}")
+        })
+    }
+
+    /// 检查能否投出 `num` 张月票，超过 `limit` 或剩余月票不足时返回 [`ActionError`]
+    pub fn check_vote(&self, num: usize, limit: usize) -> Result<()> {
+        let available = self.ticket_info()?.ticketNum;
+        Ok(ActionError::check_vote(available, num, limit)?)
+    }
+
+    /// 检查能否打赏 `amount` 火卷，超过 `limit` 或余额不足时返回 [`ActionError`]
+    pub fn check_bonus(&self, amount: usize, limit: usize) -> Result<()> {
+        let available = self.money()?.fireMoneyRemain;
+        Ok(ActionError::check_bonus(available, amount, limit)?)
+    }
+
+    /// 为小说投月票，不检查剩余月票，调用前应先调用 [`Proxy::check_vote`]
+    ///
+    /// 未经抓包验证，接口与请求体 `{ "num": ... }` 为推测，响应格式不符时返回错误
+    pub fn vote_tickets(&self, novel_id: i32, num: usize) -> Result<Value> {
+        let resp = self
+            .request(Method::POST, &format!("/novels/{novel_id}/ticket"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(json!({ "num": num }).to_string())
+            .send()?;
+        unpack_unverified_resp(resp)
+    }
+
+    /// 使用火卷打赏小说，不检查余额，调用前应先调用 [`Proxy::check_bonus`]
+    ///
+    /// 未经抓包验证，接口与请求体 `{ "num": ... }` 为推测，响应格式不符时返回错误
+    pub fn send_bonus(&self, novel_id: i32, amount: usize) -> Result<Value> {
+        let resp = self
+            .request(Method::POST, &format!("/novels/{novel_id}/bonus"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(json!({ "num": amount }).to_string())
+            .send()?;
+        unpack_unverified_resp(resp)
+    }
+
+    /// 签到，`date` 的格式为 YYYY-MM-DD
+    ///
+    /// 未经抓包验证，接口与请求体 `{ "signDate": ... }` 为推测，响应格式不符时返回错误
+    pub fn sign_in(&self, date: &str) -> Result<Value> {
+        let resp = self
+            .request(Method::PUT, "/user/newSignInfo")
+            .header(CONTENT_TYPE, "application/json")
+            .body(json!({ "signDate": date }).to_string())
+            .send()?;
+        unpack_unverified_resp(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_unverified_rejects_unexpected_shapes() {
+        let ok = r#"{"status":{"httpCode":200,"errorCode":200,"msgType":0,"msg":null},"data":{"ticketNum":3}}"#;
+        assert_eq!(
+            unpack_unverified(200, ok).unwrap(),
+            json!({ "ticketNum": 3 })
+        );
+
+        let failed = r#"{"status":{"httpCode":400,"errorCode":1,"msgType":0,"msg":"no tickets"},"data":null}"#;
+        assert_eq!(
+            unpack_unverified(400, failed).unwrap_err().to_string(),
+            "no tickets"
+        );
 
+        assert!(unpack_unverified(200, "<html></html>").is_err());
+        assert!(unpack_unverified(200, r#"{"data":{}}"#).is_err());
+        let no_data = r#"{"status":{"httpCode":200,"errorCode":200,"msgType":0,"msg":null}}"#;
+        assert!(unpack_unverified(200, no_data).is_err());
+    }
 }
@@ -0,0 +1,30 @@
+use super::types;
+use crate::{consts, request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+impl Proxy {
+    pub fn album_info(&self, album_id: i32) -> Result<types::Album> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/albums/{album_id}"))
+            .query(&[("expand", consts::FULLEXPAND["albums"])])
+            .send()?);
+    }
+
+    /// 获取有声小说的音频列表，列表中不含音频地址
+    pub fn album_chapters_of(&self, album_id: i32) -> Result<Vec<types::AlbumChapter>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/albums/{album_id}/chaps"))
+            .send()?);
+    }
+
+    /// 获取音频详情，已解锁的音频带有音频地址
+    pub fn album_chapter(&self, album_id: i32, chapter_id: i32) -> Result<types::AlbumChapter> {
+        unpack_sfresp!(self
+            .request(
+                Method::GET,
+                &format!("/albums/{album_id}/chaps/{chapter_id}")
+            )
+            .send()?);
+    }
+}
@@ -131,6 +131,12 @@ impl Proxy {
         authenticated
     }
 
+    /// 获取当前活跃的凭证
+    pub fn credential(&self) -> Option<AuthCredential> {
+        let auth = serde_json::from_value::<AuthStatus>(self.load("auth")?.to_owned()).ok()?;
+        auth.credentials.into_iter().nth(auth.status.1)
+    }
+
     pub fn profile(&self) -> Result<types::UserPrivate> {
         if self.is_authenticated() {
             unpack_sfresp!(self
@@ -0,0 +1,49 @@
+use super::types;
+use crate::{consts, request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+/// 获取全部作者公告时最多请求的页数，避免服务端持续返回满页时无限请求
+const MAX_ANNOUNCEMENT_PAGES: usize = 50;
+
+impl Proxy {
+    /// 获取当前用户的作者信息
+    pub fn author_info(&self) -> Result<types::AuthorInfo> {
+        unpack_sfresp!(self.request(Method::GET, "/user/authorInfo").send()?);
+    }
+
+    /// 获取当前用户发布的作者公告
+    pub fn author_announcements(
+        &self,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Announcement>> {
+        unpack_sfresp!(self
+            .request(Method::GET, "/user/authorAnnouncements")
+            .query(&[("page", page), ("size", size)])
+            .send()?);
+    }
+
+    /// 获取当前用户发布的全部作者公告，最多获取 `MAX_ANNOUNCEMENT_PAGES` 页
+    pub fn all_author_announcements(&self) -> Result<Vec<types::Announcement>> {
+        let size = 20;
+        let mut announcements = vec![];
+        for page in 0..MAX_ANNOUNCEMENT_PAGES {
+            let items = self.author_announcements(page, size)?;
+            let done = items.len() < size;
+            announcements.extend(items);
+            if done {
+                break;
+            }
+        }
+        Ok(announcements)
+    }
+
+    /// 获取用户发布的小说
+    pub fn novels_of(&self, user_id: i32) -> Result<Vec<types::Novel>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/users/{user_id}/novels"))
+            .query(&[("expand", consts::FULLEXPAND["novels"])])
+            .send()?);
+    }
+}
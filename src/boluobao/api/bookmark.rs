@@ -0,0 +1,46 @@
+use super::types;
+use crate::{request::*, unpack_sfresp, Proxy, Value};
+
+use anyhow::Result;
+use serde_json::json;
+
+impl Proxy {
+    /// 获取用户阅读小说时的书签，没有书签时返回 `None`
+    ///
+    /// 未经抓包验证，推测没有书签时服务端返回 404 或空的 `data`
+    pub fn bookmark_of(&self, novel_id: i32) -> Result<Option<types::Bookmark>> {
+        let resp = self
+            .request(Method::GET, "/bookmark")
+            .query(&[("novelId", novel_id)])
+            .send()?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        unpack_sfresp!(resp);
+    }
+
+    /// 更新用户阅读小说时的书签
+    ///
+    /// 未经抓包验证，请求方法与请求体为推测
+    pub fn set_bookmark(&self, novel_id: i32, chapter_id: i32, row: usize) -> Result<Value> {
+        let bookmark = json!({
+            "novelId": novel_id,
+            "chapId": chapter_id,
+            "rowNum": row,
+        });
+        let resp = self
+            .request(Method::POST, "/bookmark")
+            .header(CONTENT_TYPE, "application/json")
+            .body(bookmark.to_string())
+            .send()?;
+        unpack_sfresp!(resp);
+    }
+
+    /// 获取用户的小说阅读记录
+    pub fn read_history(&self, page: usize, size: usize) -> Result<Vec<types::NovelView>> {
+        unpack_sfresp!(self
+            .request(Method::GET, "/user/NovelViews")
+            .query(&[("page", page), ("size", size)])
+            .send()?);
+    }
+}
@@ -0,0 +1,57 @@
+use super::types;
+use crate::{consts, request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+impl Proxy {
+    pub fn chapter_content(&self, chapter_id: i32) -> Result<types::ChapterContent> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/Chaps/{chapter_id}"))
+            .query(&[
+                ("expand", consts::FULLEXPAND["chaps"]),
+                ("autoOrder", "false")
+            ])
+            .send()?);
+    }
+
+    /// 获取章节段落的吐槽（段评），`row` 为段落所在的行号
+    pub fn tsukkomis_of(
+        &self,
+        chapter_id: i32,
+        row: usize,
+        sort: &str,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Tsukkomi>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/chaps/0/{chapter_id}/tsukkomis"))
+            .query(&[
+                ("expand", consts::FULLEXPAND["chaps/tsukkomis"].to_string()),
+                ("sort", sort.to_string()),
+                ("row", row.to_string()),
+                ("page", page.to_string()),
+                ("size", size.to_string()),
+            ])
+            .send()?);
+    }
+
+    /// 获取小说的弹幕
+    pub fn barrages_of(
+        &self,
+        novel_id: i32,
+        type_id: i32,
+        is_big: bool,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Barrage>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/novels/{novel_id}/cmts/barrage"))
+            .query(&[
+                ("typeId", type_id.to_string()),
+                ("isBig", if is_big { "1" } else { "2" }.to_string()),
+                ("page", page.to_string()),
+                ("size", size.to_string()),
+            ])
+            .send()?);
+    }
+}
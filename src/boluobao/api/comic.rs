@@ -0,0 +1,20 @@
+use super::types;
+use crate::{consts, request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+impl Proxy {
+    pub fn comic_info(&self, comic_id: i32) -> Result<types::Comic> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/comics/{comic_id}"))
+            .query(&[("expand", consts::FULLEXPAND["comics"])])
+            .send()?);
+    }
+
+    /// 获取漫画的章节列表
+    pub fn comic_chapters_of(&self, comic_id: i32) -> Result<Vec<types::ComicChapter>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/comics/{comic_id}/chaps"))
+            .send()?);
+    }
+}
@@ -0,0 +1,87 @@
+use super::types;
+use crate::{request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+impl Proxy {
+    /// 获取小说的评论
+    ///
+    /// `type` 可选 clear、stick、stickandclear，`sort` 可选 smart、timeline
+    pub fn comments_of(
+        &self,
+        novel_id: i32,
+        r#type: &str,
+        sort: &str,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Comment>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/novels/{novel_id}/Cmts"))
+            .query(&[
+                ("page", page.to_string()),
+                ("size", size.to_string()),
+                ("type", r#type.to_string()),
+                ("sort", sort.to_string()),
+                ("replyUserId", "0".to_string()),
+            ])
+            .send()?);
+    }
+
+    /// 获取小说的长评（书评）
+    ///
+    /// `sort` 可选 addtime，`charlen` 为内容的截断长度
+    pub fn long_comments_of(
+        &self,
+        novel_id: i32,
+        sort: &str,
+        charlen: usize,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::LongComment>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/novels/{novel_id}/lcmts"))
+            .query(&[
+                ("page", page.to_string()),
+                ("size", size.to_string()),
+                ("sort", sort.to_string()),
+                ("charlen", charlen.to_string()),
+            ])
+            .send()?);
+    }
+
+    pub fn comment(&self, comment_id: i32) -> Result<types::Comment> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/cmts/{comment_id}"))
+            .send()?);
+    }
+
+    pub fn comment_replies(
+        &self,
+        comment_id: i32,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Reply>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/cmts/{comment_id}/replys"))
+            .query(&[("page", page), ("size", size)])
+            .send()?);
+    }
+
+    pub fn long_comment(&self, comment_id: i32) -> Result<types::LongComment> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/lcmts/{comment_id}"))
+            .send()?);
+    }
+
+    pub fn long_comment_replies(
+        &self,
+        comment_id: i32,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::Reply>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/lcmts/{comment_id}/replys"))
+            .query(&[("page", page), ("size", size)])
+            .send()?);
+    }
+}
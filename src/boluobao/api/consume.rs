@@ -0,0 +1,72 @@
+use super::types;
+use crate::{request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+/// 获取完整消费记录时最多请求的页数，避免服务端持续返回满页时无限请求
+const MAX_CONSUME_PAGES: usize = 200;
+
+impl Proxy {
+    pub fn money(&self) -> Result<types::Money> {
+        unpack_sfresp!(self.request(Method::GET, "/user/money").send()?);
+    }
+
+    pub fn consumes(
+        &self,
+        kinds: &[&str],
+        entity_id: Option<i32>,
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::ConsumeRecord>> {
+        let mut query = vec![("page", page.to_string()), ("size", size.to_string())];
+        if !kinds.is_empty() {
+            query.push(("type", kinds.join(",")));
+        }
+        if let Some(entity_id) = entity_id {
+            query.push(("entityId", entity_id.to_string()));
+        }
+        unpack_sfresp!(self
+            .request(Method::GET, "/user/consumes")
+            .query(&query)
+            .send()?);
+    }
+
+    pub fn consume_items(
+        &self,
+        kinds: &[&str],
+        page: usize,
+        size: usize,
+    ) -> Result<Vec<types::ConsumeItem>> {
+        let mut query = vec![("page", page.to_string()), ("size", size.to_string())];
+        if !kinds.is_empty() {
+            query.push(("type", kinds.join(",")));
+        }
+        unpack_sfresp!(self
+            .request(Method::GET, "/user/consumeitems")
+            .query(&query)
+            .send()?);
+    }
+
+    /// 获取完整的消费记录，最多获取 `MAX_CONSUME_PAGES` 页，超出时截断并打印警告
+    pub fn consume_history(
+        &self,
+        kinds: &[&str],
+        entity_id: Option<i32>,
+    ) -> Result<Vec<types::ConsumeRecord>> {
+        let size = 50;
+        let mut history = vec![];
+        for page in 0..MAX_CONSUME_PAGES {
+            let records = self.consumes(kinds, entity_id, page, size)?;
+            let done = records.len() < size;
+            history.extend(records);
+            if done {
+                return Ok(history);
+            }
+        }
+        eprintln!(
+            "warning: consume history is truncated to the latest {} records",
+            history.len()
+        );
+        Ok(history)
+    }
+}
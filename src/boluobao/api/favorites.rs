@@ -1,5 +1,5 @@
 use super::types;
-use crate::{consts, request::*, unpack_sfresp, Proxy};
+use crate::{consts, request::*, unpack_sfresp, Favoirtes, Proxy, Type};
 
 use anyhow::Result;
 
@@ -10,4 +10,27 @@ impl Proxy {
             .query(&[("expand", consts::FULLEXPAND["user/pockets"])])
             .send()?);
     }
+
+    /// 获取收藏夹并逐个获取漫画收藏夹中漫画的完整信息与章节列表
+    ///
+    /// 单部漫画获取失败时保留收藏夹给出的摘要并打印警告
+    pub fn favoirtes_with_comics(&self) -> Result<Vec<Favoirtes>> {
+        let mut favorites = vec![];
+        for pocket in self.favoirtes()? {
+            let mut pocket = pocket.parse()?;
+            if pocket.r#type == Type::Comic {
+                for comic in pocket.comics.iter_mut() {
+                    let full = self
+                        .comic_info(comic.id)
+                        .and_then(|e| e.parse(self.comic_chapters_of(comic.id)?));
+                    match full {
+                        Ok(full) => *comic = full,
+                        Err(err) => eprintln!("warning: failed to get comic {}: {err}", comic.id),
+                    }
+                }
+            }
+            favorites.push(pocket);
+        }
+        Ok(favorites)
+    }
 }
@@ -1,10 +1,17 @@
 pub mod types;
 
 pub mod action;
+pub mod album;
 pub mod auth;
+pub mod author;
+pub mod bookmark;
 pub mod chapter;
+pub mod comic;
+pub mod comment;
+pub mod consume;
 pub mod favorites;
 pub mod novel;
+pub mod rank;
 pub mod search;
 pub mod user;
 
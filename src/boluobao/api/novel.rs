@@ -21,4 +21,12 @@ impl Proxy {
             .query(&[("expand", consts::FULLEXPAND["novels/dirs"])])
             .send()?);
     }
+
+    /// 获取小说的衍生作品（漫画与有声小说）
+    pub fn derivative_works_of(&self, novel_id: i32) -> Result<types::DerivativeWorks> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/adpworks/novelId/{novel_id}"))
+            .query(&[("expand", "signlevel")])
+            .send()?);
+    }
 }
@@ -0,0 +1,82 @@
+use super::types;
+use crate::{request::*, unpack_sfresp, Proxy};
+
+use anyhow::Result;
+
+impl Proxy {
+    /// 获取小说的月票榜，`date_range` 为 1（日）、2（周）或 3（月）
+    ///
+    /// 抓包只见到 `dateRange=1`，其余取值与含义未经抓包验证，为推测
+    pub fn ticket_rank_of(
+        &self,
+        novel_id: i32,
+        num_max: usize,
+        date_range: i32,
+    ) -> Result<Vec<types::RankEntry>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/novels/{novel_id}/ticket/rank"))
+            .query(&[
+                ("numMax", num_max.to_string()),
+                ("dateRange", date_range.to_string()),
+                ("expand", "avatar".to_string()),
+            ])
+            .send()?);
+    }
+
+    /// 获取小说的打赏榜，`date_range` 同 [`Proxy::ticket_rank_of`]，其取值未经抓包验证
+    pub fn bonus_rank_of(
+        &self,
+        novel_id: i32,
+        num_max: usize,
+        date_range: i32,
+    ) -> Result<Vec<types::RankEntry>> {
+        unpack_sfresp!(self
+            .request(Method::GET, &format!("/novels/{novel_id}/bonus/rank"))
+            .query(&[
+                ("numMax", num_max.to_string()),
+                ("dateRange", date_range.to_string()),
+                ("expand", "avatar".to_string()),
+            ])
+            .send()?);
+    }
+
+    /// 获取当前用户在小说月票榜中的排名，`date_range` 同 [`Proxy::ticket_rank_of`]，其取值未经抓包验证
+    pub fn user_ticket_rank_of(
+        &self,
+        novel_id: i32,
+        num_max: usize,
+        date_range: i32,
+    ) -> Result<types::UserRankingList> {
+        unpack_sfresp!(self
+            .request(
+                Method::GET,
+                &format!("/user/novel/{novel_id}/ticket/rankinglist")
+            )
+            .query(&[
+                ("numMax", num_max.to_string()),
+                ("dateRange", date_range.to_string()),
+                ("expand", "avatar".to_string()),
+            ])
+            .send()?);
+    }
+
+    /// 获取当前用户在小说打赏榜中的排名，`date_range` 同 [`Proxy::ticket_rank_of`]，其取值未经抓包验证
+    pub fn user_bonus_rank_of(
+        &self,
+        novel_id: i32,
+        num_max: usize,
+        date_range: i32,
+    ) -> Result<types::UserRankingList> {
+        unpack_sfresp!(self
+            .request(
+                Method::GET,
+                &format!("/user/novel/{novel_id}/bonus/rankinglist")
+            )
+            .query(&[
+                ("numMax", num_max.to_string()),
+                ("dateRange", date_range.to_string()),
+                ("expand", "avatar".to_string()),
+            ])
+            .send()?);
+    }
+}
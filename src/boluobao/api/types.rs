@@ -100,16 +100,16 @@ pub struct User {
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct SystemTag {
-    sysTagId: i32,
-    tagName: String,
+    pub sysTagId: i32,
+    pub tagName: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct RankingList {
-    dateRange: i32,
-    desc: String,
-    r#type: i32,
+    pub dateRange: i32,
+    pub desc: String,
+    pub r#type: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -257,19 +257,65 @@ pub struct ComicRef {
     pub comicCover: String,
     pub comicId: i32,
     pub comicName: String,
-    pub folderName: String,
+    pub folderName: Option<String>,
     pub isFinished: bool,
-    pub isSticky: bool,
+    pub isSticky: Option<bool>,
     pub lastUpdateTime: String,
     pub latestChapterTitle: String,
     pub point: f32,
-    pub markDateTime: String,
+    pub markDateTime: Option<String>,
     pub signStatus: String,
     pub stickyDateTime: Option<String>,
     pub typeId: i32,
     pub viewTimes: usize,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ComicExpand {
+    pub authorName: Option<String>,
+    pub intro: Option<String>,
+    pub typeName: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub sysTags: Option<Vec<SystemTag>>,
+    pub chapterCount: Option<usize>,
+    pub fav: Option<usize>,
+    pub ticket: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Comic {
+    pub authorId: i32,
+    pub bgBanner: String,
+    pub comicCover: String,
+    pub comicId: i32,
+    pub comicName: String,
+    pub folderName: String,
+    pub isFinished: bool,
+    pub lastUpdateTime: String,
+    pub latestChapterTitle: Option<String>,
+    pub point: f32,
+    pub signStatus: String,
+    pub typeId: i32,
+    pub viewTimes: usize,
+    pub expand: Option<ComicExpand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ComicChapter {
+    pub comicId: i32,
+    pub chapId: i32,
+    pub title: String,
+    pub chapOrder: i32,
+    pub isVip: bool,
+    pub needFireMoney: usize,
+    pub originNeedFireMoney: Option<usize>,
+    pub addTime: String,
+    pub updateTime: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct AlbumRef {
@@ -282,11 +328,62 @@ pub struct AlbumRef {
     pub coverSmall: String,
     pub coverMedium: String,
     pub coverBig: String,
-    pub isSticky: bool,
+    pub isSticky: Option<bool>,
     pub stickyDateTime: Option<String>,
     pub visitTimes: usize,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct AlbumExpand {
+    pub authorName: Option<String>,
+    pub intro: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub sysTags: Option<Vec<SystemTag>>,
+    pub chapterCount: Option<usize>,
+    pub novelName: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Album {
+    pub authorId: i32,
+    pub novelId: i32,
+    pub albumId: i32,
+    pub name: String,
+    pub latestChapterId: i32,
+    pub lastUpdateTime: String,
+    pub coverSmall: String,
+    pub coverMedium: String,
+    pub coverBig: String,
+    pub visitTimes: usize,
+    pub isFinished: Option<bool>,
+    pub expand: Option<AlbumExpand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct AlbumChapter {
+    pub albumId: i32,
+    pub chapId: i32,
+    pub title: String,
+    pub sno: f32,
+    pub duration: Option<usize>,
+    pub isVip: bool,
+    pub needFireMoney: usize,
+    pub addTime: String,
+    pub ossUrl: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct DerivativeWorks {
+    #[serde(default)]
+    pub comics: Vec<ComicRef>,
+    #[serde(default)]
+    pub albums: Vec<AlbumRef>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PocketExpand {
@@ -311,10 +408,10 @@ pub struct Pocket {
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct SignInfo {
-    year: i32,        //<! 签到年
-    month: i32,       //<! 签到月
-    day: i32,         //<! 签到日
-    continueNum: i32, //<! 连续签到的天数
+    pub year: i32,        //<! 签到年
+    pub month: i32,       //<! 签到月
+    pub day: i32,         //<! 签到日
+    pub continueNum: i32, //<! 连续签到的天数
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -348,3 +445,199 @@ pub struct SearchResult {
     pub comics: Vec<ComicRef>,
     pub novels: Vec<NovelRecord>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Money {
+    pub fireMoneyUsed: usize,   //<! 累计消费的火卷
+    pub fireMoneyRemain: usize, //<! 剩余火卷
+    pub vipLevel: usize,        //<! VIP 等级
+    pub couponsRemain: usize,   //<! 剩余代金卷
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ConsumeRecord {
+    pub entityId: i32,
+    pub entityName: Option<String>,
+    pub chapId: Option<i32>,
+    pub title: Option<String>,
+    pub r#type: String,
+    pub fireMoney: usize,
+    pub couponMoney: Option<usize>,
+    pub consumeDate: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ConsumeItem {
+    pub entityId: i32,
+    pub entityName: String,
+    pub r#type: String,
+    pub totalFireMoney: usize,
+    pub totalCouponMoney: Option<usize>,
+    pub lastConsumeDate: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Comment {
+    pub commentId: i32,
+    pub novelId: i32,
+    pub userId: i32,
+    pub nickName: String,
+    pub content: String,
+    pub addTime: String,
+    pub replyNum: usize,
+    pub praiseNum: usize,
+    pub isStick: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct LongComment {
+    pub lcommentId: i32,
+    pub novelId: i32,
+    pub userId: i32,
+    pub nickName: String,
+    pub title: String,
+    pub content: String,
+    pub charCount: Option<usize>,
+    pub addTime: String,
+    pub replyNum: usize,
+    pub praiseNum: usize,
+    pub isStick: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Reply {
+    pub replyId: i32,
+    #[serde(alias = "lcommentId")]
+    pub commentId: i32,
+    pub userId: i32,
+    pub nickName: String,
+    pub content: String,
+    pub addTime: String,
+    pub replyUserId: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ChapterContentExpand {
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct ChapterContent {
+    pub chapId: i32,
+    pub novelId: i32,
+    pub volumeId: i32,
+    pub title: String,
+    pub charCount: usize,
+    pub rowNum: usize,
+    pub isVip: bool,
+    pub expand: Option<ChapterContentExpand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct TsukkomiExpand {
+    pub vipLevel: Option<usize>,
+    pub avatar: Option<String>,
+    pub roleName: Option<String>,
+    pub widgets: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Tsukkomi {
+    pub tsukkomiId: i32,
+    pub chapId: i32,
+    pub row: usize,
+    pub userId: i32,
+    pub nickName: String,
+    pub content: String,
+    pub praiseNum: usize,
+    pub addTime: String,
+    pub expand: Option<TsukkomiExpand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Barrage {
+    pub barrageId: i32,
+    pub novelId: i32,
+    pub chapId: Option<i32>,
+    pub userId: i32,
+    pub nickName: String,
+    pub content: String,
+    pub addTime: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct RankEntryExpand {
+    pub avatar: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct RankEntry {
+    pub userId: i32,
+    pub nickName: String,
+    pub num: usize,
+    pub expand: Option<RankEntryExpand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct UserRankingList {
+    pub myRank: Option<usize>,
+    pub myNum: usize,
+    pub rankList: Vec<RankEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct TicketInfo {
+    pub ticketNum: usize, //<! 剩余月票
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Bookmark {
+    pub novelId: i32,
+    pub chapId: i32,
+    pub rowNum: Option<usize>,
+    pub updateTime: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct NovelView {
+    pub novelId: i32,
+    pub novelName: String,
+    pub chapId: i32,
+    pub chapTitle: Option<String>,
+    pub viewTime: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct AuthorInfo {
+    pub authorId: i32,
+    pub accountId: i32,
+    pub authorName: String,
+    pub avatar: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Announcement {
+    pub announcementId: i32,
+    pub title: String,
+    pub content: String,
+    pub addTime: String,
+}
@@ -22,4 +22,9 @@ impl Proxy {
             .query(&[("expand", consts::FULLEXPAND["user"])])
             .send()?);
     }
+
+    /// 获取当前用户的签到记录
+    pub fn sign_info(&self) -> Result<Vec<types::SignInfo>> {
+        unpack_sfresp!(self.request(Method::GET, "/user/signInfo").send()?);
+    }
 }
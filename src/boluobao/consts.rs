@@ -9,8 +9,12 @@ pub const APPKEYS: Map<&'static str, &'static str> = phf_map! {
 };
 
 pub const FULLEXPAND: Map<&'static str, &'static str> = phf_map! {
+    "albums" => "authorName,intro,tags,sysTags,chapterCount,novelName",
+    "comics" => "authorName,intro,typeName,tags,sysTags,chapterCount,fav,ticket",
     "novels" => "chapterCount,bigBgBanner,bigNovelCover,typeName,intro,fav,ticket,pointCount,tags,sysTags,signlevel,discount,discountExpireDate,totalNeedFireMoney,rankinglist,originTotalNeedFireMoney,firstchapter,latestchapter,latestcommentdate,essaytag,auditCover,preOrderInfo,customTag,topic,unauditedCustomtag,homeFlag,isbranch",
     "novels/dirs" => "originNeedFireMoney",
+    "chaps" => "content",
+    "chaps/tsukkomis" => "vipLevel,avatar,roleName,widgets",
     "user" => "introduction,bigAvatar,avatar,backgroundPic,fansNum,followNum,followyou,youfollow,verifyType,verifyInfo,avatarFrame,youblock,widgets,growup",
     "user/private" => "changeNickNameInfo,hasActiveUnlockChapWithAd,hasOrderChapWithFireMoney,hasOrderedVipChaps,hasPaidFirstTime,hasUnlockChapWithAd,isRealNameAuth,redpacketCode,usedRedpacketCode,useWelfaresys,vipInfo,welfareCoin,welfareMoney",
     "user/pockets" => "comics,novels,albums",
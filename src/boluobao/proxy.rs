@@ -2,14 +2,17 @@ use super::consts;
 use crate::Value;
 
 use anyhow::{bail, Result};
-use serde::{Deserialize, Serialize};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Map;
+use std::sync::OnceLock;
 use uuid::Uuid;
 
 struct Config {
     version: String,
     channel: String,
     device_token: String,
+    http_proxy: Option<String>,
 }
 
 pub struct ProxyBuilder {
@@ -21,7 +24,32 @@ pub struct Proxy {
     version: String,
     channel: String,
     device_token: String,
+    #[serde(default, deserialize_with = "deserialize_http_proxy")]
+    http_proxy: Option<String>,
     cache: Map<String, Value>,
+    #[serde(skip)]
+    client: OnceLock<Client>,
+}
+
+/// 反序列化时检查代理地址，避免在发送请求时才发现地址无效
+fn deserialize_http_proxy<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let url = Option::<String>::deserialize(deserializer)?;
+    if let Some(url) = &url {
+        reqwest::Proxy::all(url).map_err(serde::de::Error::custom)?;
+    }
+    Ok(url)
+}
+
+/// 创建发送请求的客户端，`http_proxy` 为 HTTP 代理的地址
+fn new_client(http_proxy: Option<&str>) -> Result<Client> {
+    let builder = Client::builder();
+    let builder = match http_proxy {
+        Some(url) => builder.proxy(reqwest::Proxy::all(url)?),
+        None => builder,
+    };
+    Ok(builder.build()?)
 }
 
 impl ProxyBuilder {
@@ -33,18 +61,22 @@ impl ProxyBuilder {
                 version: version,
                 channel: "HomePage".to_string(),
                 device_token: device_token,
+                http_proxy: None,
             },
         }
     }
 
     pub fn build(self) -> Result<Proxy> {
         let config = self.config;
+        let client = new_client(config.http_proxy.as_deref())?;
         if consts::APPKEYS.contains_key(&config.version) {
             Ok(Proxy {
                 version: config.version,
                 channel: config.channel,
                 device_token: config.device_token,
+                http_proxy: config.http_proxy,
                 cache: Map::<String, Value>::new(),
+                client: OnceLock::from(client),
             })
         } else {
             bail!("invalid app version: {}", config.version);
@@ -65,6 +97,12 @@ impl ProxyBuilder {
         self.config.device_token = device_token.to_string();
         self
     }
+
+    /// 通过 HTTP 代理发送请求
+    pub fn with_http_proxy(mut self, url: &str) -> Self {
+        self.config.http_proxy = Some(url.to_string());
+        self
+    }
 }
 
 impl Proxy {
@@ -88,6 +126,19 @@ impl Proxy {
         &self.device_token
     }
 
+    pub fn get_http_proxy(&self) -> Option<&String> {
+        self.http_proxy.as_ref()
+    }
+
+    /// 所有请求共用的客户端，反序列化得到的 `Proxy` 在首次请求时创建客户端
+    ///
+    /// 代理地址已在创建或反序列化时检查，此处只在 TLS 后端初始化失败时 panic，与 `Client::new` 一致
+    pub(crate) fn http_client(&self) -> &Client {
+        self.client.get_or_init(|| {
+            new_client(self.http_proxy.as_deref()).expect("failed to initialize the HTTP client")
+        })
+    }
+
     pub fn is_cached(&self, key: &str) -> bool {
         self.cache.contains_key(key)
     }
@@ -103,4 +154,32 @@ impl Proxy {
     pub fn store(&mut self, key: &str, value: Value) -> Option<Value> {
         self.cache.insert(key.to_string(), value)
     }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.cache.remove(key)
+    }
+
+    /// 只保留 `f` 返回真的缓存项
+    pub fn retain(&mut self, mut f: impl FnMut(&String, &Value) -> bool) {
+        self.cache.retain(|key, value| f(key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_http_proxy_is_rejected() {
+        let proxy = Proxy::builder().build().unwrap();
+        let mut value = serde_json::to_value(&proxy).unwrap();
+        assert!(serde_json::from_value::<Proxy>(value.clone()).is_ok());
+
+        value["http_proxy"] = "not a url".into();
+        assert!(serde_json::from_value::<Proxy>(value).is_err());
+        assert!(Proxy::builder()
+            .with_http_proxy("not a url")
+            .build()
+            .is_err());
+    }
 }
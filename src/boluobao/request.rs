@@ -1,9 +1,6 @@
 use super::{api::auth::AuthStatus, *};
 
-use reqwest::{
-    blocking::{Client, RequestBuilder},
-    header::HeaderMap,
-};
+use reqwest::{blocking::RequestBuilder, header::HeaderMap};
 pub use reqwest::{
     header::{ACCEPT, ACCEPT_CHARSET, AUTHORIZATION, CONTENT_TYPE, COOKIE, SET_COOKIE, USER_AGENT},
     Method,
@@ -45,8 +42,8 @@ impl Proxy {
 
     pub fn request(&self, method: Method, api: &str) -> RequestBuilder {
         let prefix = consts::APIPREFIX;
-        let client = Client::new();
-        let client = client
+        let client = self
+            .http_client()
             .request(method, format!("{prefix}{api}"))
             .headers(self.default_headers());
         if self.is_authenticated() {
@@ -74,8 +71,8 @@ impl Proxy {
         session: &str,
     ) -> RequestBuilder {
         let prefix = consts::APIPREFIX;
-        let client = Client::new();
-        let client = client
+        let client = self
+            .http_client()
             .request(method, format!("{prefix}{api}"))
             .headers(self.default_headers());
         client.header(
@@ -8,10 +8,22 @@ impl types::Pocket {
     pub fn parse(self) -> Result<Favoirtes> {
         let r#type = Type::from(self.typeId);
         let expand = self.expand.unwrap();
+        let mut comics = vec![];
+        let mut albums = vec![];
         let refs: Vec<Id> = match r#type {
-            Type::Comic => expand.comics.unwrap().iter().map(|e| e.comicId).collect(),
+            Type::Comic => {
+                for comic in expand.comics.unwrap_or_default() {
+                    comics.push(comic.parse_comic()?);
+                }
+                comics.iter().map(|e| e.id).collect()
+            }
             Type::Novel => expand.novels.unwrap().iter().map(|e| e.novelId).collect(),
-            Type::Album => expand.albums.unwrap().iter().map(|e| e.albumId).collect(),
+            Type::Album => {
+                for album in expand.albums.unwrap_or_default() {
+                    albums.push(album.parse_album()?);
+                }
+                albums.iter().map(|e| e.id).collect()
+            }
         };
         Ok(Favoirtes {
             id: self.pocketId,
@@ -22,6 +34,8 @@ impl types::Pocket {
             modifiable: self.canModify,
             is_full: self.isFull,
             refs: refs,
+            comics,
+            albums,
         })
     }
 }
@@ -49,7 +63,7 @@ impl types::User {
 
 impl types::UserPrivate {
     #[inline]
-    pub fn parse(self) -> Result<UserPrivate> {
+    pub fn parse(self, money: types::Money) -> Result<UserPrivate> {
         let expand = self.expand.unwrap();
         let vip_info = expand.vipInfo.unwrap();
         let vip = VipInfo {
@@ -64,15 +78,21 @@ impl types::UserPrivate {
         };
         Ok(UserPrivate {
             id: self.accountId,
+            nickname: self.nickName,
             area_code: self.countryCode,
             phone: self.phoneNum,
             email: self.email,
             is_author: self.isAuthor,
             vip: vip,
             registration_time: to_timestamp(&self.registerDate)?,
-            banlance: 0, //<! FIXME
-            vouchers: 0, //<! FIXME
+            banlance: money.fireMoneyRemain,
+            vouchers: money.couponsRemain,
             tokens: expand.welfareCoin.unwrap() as usize,
+            welfare_money: expand.welfareMoney.unwrap_or_default(),
+            nickname_change: expand.changeNickNameInfo.map(|e| NicknameChange {
+                can_change: e.canChange,
+                days_until_next: e.nextChangeNeedDays,
+            }),
         })
     }
 }
@@ -87,6 +107,13 @@ impl types::Novel {
             author: self.authorName,
             author_id: self.authorId,
             r#type: expand.typeName.unwrap(),
+            tags: expand
+                .sysTags
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| e.tagName)
+                .chain(expand.tags.unwrap_or_default())
+                .collect(),
             intro: expand.intro.unwrap(),
             sign_status: self.signStatus,
             sign_level: expand.signLevel.unwrap(),
@@ -129,3 +156,423 @@ impl types::Chapter {
         })
     }
 }
+
+impl types::Volume {
+    #[inline]
+    pub fn parse(self, novel_id: Id) -> Result<Volume> {
+        Ok(Volume {
+            novel_id,
+            id: self.volumeId,
+            title: self.title,
+            order: self.sno as usize,
+            chapters: self
+                .chapterList
+                .into_iter()
+                .map(|e| e.parse())
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+impl types::Catalogue {
+    #[inline]
+    pub fn parse(self) -> Result<Vec<Volume>> {
+        let novel_id = self.novelId;
+        self.volumeList
+            .into_iter()
+            .map(|e| e.parse(novel_id))
+            .collect()
+    }
+}
+
+impl types::ConsumeRecord {
+    #[inline]
+    pub fn parse(self) -> Result<Consumption> {
+        Ok(Consumption {
+            novel_id: self.entityId,
+            novel_name: self.entityName,
+            chapter_id: self.chapId,
+            title: self.title,
+            r#type: self.r#type,
+            fire_coins: self.fireMoney,
+            vouchers: self.couponMoney.unwrap_or_default(),
+            time: to_unix_timestamp(&self.consumeDate)?,
+        })
+    }
+}
+
+impl types::Comment {
+    #[inline]
+    pub fn parse(self) -> Result<Comment> {
+        Ok(Comment {
+            id: self.commentId,
+            novel_id: self.novelId,
+            user_id: self.userId,
+            nickname: self.nickName,
+            title: None,
+            content: self.content,
+            is_long: false,
+            is_sticky: self.isStick,
+            total_likes: self.praiseNum,
+            total_replies: self.replyNum,
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::LongComment {
+    #[inline]
+    pub fn parse(self) -> Result<Comment> {
+        Ok(Comment {
+            id: self.lcommentId,
+            novel_id: self.novelId,
+            user_id: self.userId,
+            nickname: self.nickName,
+            title: Some(self.title),
+            content: self.content,
+            is_long: true,
+            is_sticky: self.isStick,
+            total_likes: self.praiseNum,
+            total_replies: self.replyNum,
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::Reply {
+    #[inline]
+    pub fn parse(self) -> Result<Reply> {
+        Ok(Reply {
+            id: self.replyId,
+            comment_id: self.commentId,
+            user_id: self.userId,
+            nickname: self.nickName,
+            content: self.content,
+            reply_to: self.replyUserId.filter(|e| *e != 0),
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::Tsukkomi {
+    #[inline]
+    pub fn parse(self) -> Result<Annotation> {
+        Ok(Annotation {
+            id: self.tsukkomiId,
+            chapter_id: self.chapId,
+            row: self.row,
+            user_id: self.userId,
+            nickname: self.nickName,
+            content: self.content,
+            total_likes: self.praiseNum,
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::Barrage {
+    #[inline]
+    pub fn parse(self) -> Result<Barrage> {
+        Ok(Barrage {
+            id: self.barrageId,
+            novel_id: self.novelId,
+            chapter_id: self.chapId,
+            user_id: self.userId,
+            nickname: self.nickName,
+            content: self.content,
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::UserRankingList {
+    #[inline]
+    pub fn parse(self) -> Result<UserRank> {
+        Ok(UserRank {
+            rank: self.myRank,
+            amount: self.myNum,
+        })
+    }
+}
+
+impl types::RankEntry {
+    #[inline]
+    pub fn parse(self, rank: usize) -> Result<RankEntry> {
+        Ok(RankEntry {
+            rank,
+            user_id: self.userId,
+            nickname: self.nickName,
+            avatar: self.expand.and_then(|e| e.avatar),
+            amount: self.num,
+        })
+    }
+}
+
+impl types::NovelView {
+    #[inline]
+    pub fn parse(self) -> Result<NovelView> {
+        Ok(NovelView {
+            novel_id: self.novelId,
+            novel_name: self.novelName,
+            chapter_id: self.chapId,
+            chapter_title: self.chapTitle,
+            view_time: self.viewTime,
+        })
+    }
+}
+
+impl types::Bookmark {
+    #[inline]
+    pub fn parse(self) -> Result<ReadingProgress> {
+        Ok(ReadingProgress {
+            novel_id: self.novelId,
+            chapter_id: self.chapId,
+            row: self.rowNum.unwrap_or_default(),
+            update_time: to_unix_timestamp(&self.updateTime)?,
+        })
+    }
+}
+
+impl types::NovelRecord {
+    #[inline]
+    pub fn parse(self) -> Result<SearchHit> {
+        Ok(SearchHit {
+            r#type: Type::Novel,
+            id: self.novelId,
+            name: self.novelName,
+            author: Some(self.authorName),
+            author_id: self.authorId,
+            total_chars: Some(self.charCount),
+            total_views: self.viewTimes,
+            total_favorites: Some(self.markCount),
+            is_finished: Some(self.isFinish),
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+        })
+    }
+}
+
+impl types::ComicRef {
+    /// 转换为搜索结果
+    #[inline]
+    pub fn parse(self) -> Result<SearchHit> {
+        Ok(SearchHit {
+            r#type: Type::Comic,
+            id: self.comicId,
+            name: self.comicName,
+            author: None,
+            author_id: self.authorId,
+            total_chars: None,
+            total_views: self.viewTimes,
+            total_favorites: None,
+            is_finished: Some(self.isFinished),
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+        })
+    }
+
+    /// 转换为不含作者名、简介与章节的漫画
+    #[inline]
+    pub fn parse_comic(self) -> Result<Comic> {
+        Ok(Comic {
+            id: self.comicId,
+            name: self.comicName,
+            author: None,
+            author_id: self.authorId,
+            tags: vec![],
+            intro: None,
+            cover: self.comicCover,
+            banner: self.bgBanner,
+            total_views: self.viewTimes,
+            is_finished: self.isFinished,
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+            chapters: vec![],
+        })
+    }
+}
+
+impl types::Comic {
+    #[inline]
+    pub fn parse(self, chapters: Vec<types::ComicChapter>) -> Result<Comic> {
+        let expand = self.expand.unwrap_or_default();
+        Ok(Comic {
+            id: self.comicId,
+            name: self.comicName,
+            author: expand.authorName,
+            author_id: self.authorId,
+            tags: expand
+                .sysTags
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| e.tagName)
+                .chain(expand.tags.unwrap_or_default())
+                .collect(),
+            intro: expand.intro,
+            cover: self.comicCover,
+            banner: self.bgBanner,
+            total_views: self.viewTimes,
+            is_finished: self.isFinished,
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+            chapters: {
+                let mut chapters = chapters
+                    .into_iter()
+                    .map(|e| e.parse())
+                    .collect::<Result<Vec<_>>>()?;
+                chapters.sort_by_key(|e| e.order);
+                chapters
+            },
+        })
+    }
+}
+
+impl types::ComicChapter {
+    #[inline]
+    pub fn parse(self) -> Result<ComicChapter> {
+        let creation = to_timestamp(&self.addTime)?;
+        Ok(ComicChapter {
+            comic_id: self.comicId,
+            id: self.chapId,
+            title: self.title,
+            order: self.chapOrder as usize,
+            is_free: !self.isVip,
+            price: self.needFireMoney,
+            creation_time: creation,
+            update_time: match &self.updateTime {
+                Some(time) => to_timestamp(time)?,
+                None => creation,
+            },
+        })
+    }
+}
+
+impl types::AlbumRef {
+    #[inline]
+    pub fn parse(self) -> Result<SearchHit> {
+        Ok(SearchHit {
+            r#type: Type::Album,
+            id: self.albumId,
+            name: self.name,
+            author: None,
+            author_id: self.authorId,
+            total_chars: None,
+            total_views: self.visitTimes,
+            total_favorites: None,
+            is_finished: None,
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+        })
+    }
+
+    /// 转换为不含作者名、简介、完结状态与音频的有声小说
+    #[inline]
+    pub fn parse_album(self) -> Result<Album> {
+        Ok(Album {
+            id: self.albumId,
+            novel_id: self.novelId,
+            name: self.name,
+            author: None,
+            author_id: self.authorId,
+            tags: vec![],
+            intro: None,
+            cover: self.coverBig,
+            total_views: self.visitTimes,
+            is_finished: None,
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+            tracks: vec![],
+        })
+    }
+}
+
+impl types::Album {
+    #[inline]
+    pub fn parse(self, tracks: Vec<types::AlbumChapter>) -> Result<Album> {
+        let expand = self.expand.unwrap_or_default();
+        let mut tracks = tracks
+            .into_iter()
+            .map(|e| e.parse())
+            .collect::<Result<Vec<_>>>()?;
+        tracks.sort_by_key(|e| e.order);
+        Ok(Album {
+            id: self.albumId,
+            novel_id: self.novelId,
+            name: self.name,
+            author: expand.authorName,
+            author_id: self.authorId,
+            tags: expand
+                .sysTags
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| e.tagName)
+                .chain(expand.tags.unwrap_or_default())
+                .collect(),
+            intro: expand.intro,
+            cover: self.coverBig,
+            total_views: self.visitTimes,
+            is_finished: self.isFinished,
+            last_update_time: to_timestamp(&self.lastUpdateTime)?,
+            tracks,
+        })
+    }
+}
+
+impl types::AlbumChapter {
+    #[inline]
+    pub fn parse(self) -> Result<Track> {
+        Ok(Track {
+            album_id: self.albumId,
+            id: self.chapId,
+            title: self.title,
+            order: self.sno as usize,
+            duration: self.duration,
+            is_free: !self.isVip,
+            price: self.needFireMoney,
+            creation_time: to_timestamp(&self.addTime)?,
+            url: self.ossUrl.filter(|e| !e.is_empty()),
+        })
+    }
+}
+
+impl types::Announcement {
+    #[inline]
+    pub fn parse(self) -> Result<Announcement> {
+        Ok(Announcement {
+            id: self.announcementId,
+            title: self.title,
+            content: self.content,
+            creation_time: to_timestamp(&self.addTime)?,
+        })
+    }
+}
+
+impl types::DerivativeWorks {
+    #[inline]
+    pub fn parse(self, novel_id: Id) -> Result<DerivativeWorks> {
+        Ok(DerivativeWorks {
+            novel_id,
+            comics: self
+                .comics
+                .into_iter()
+                .map(|e| e.parse_comic())
+                .collect::<Result<Vec<_>>>()?,
+            albums: self
+                .albums
+                .into_iter()
+                .map(|e| e.parse_album())
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+impl types::SearchResult {
+    #[inline]
+    pub fn parse(self) -> Result<Vec<SearchHit>> {
+        let mut hits = vec![];
+        for novel in self.novels {
+            hits.push(novel.parse()?);
+        }
+        for comic in self.comics {
+            hits.push(comic.parse()?);
+        }
+        for album in self.albums {
+            hits.push(album.parse()?);
+        }
+        Ok(hits)
+    }
+}
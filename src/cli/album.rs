@@ -0,0 +1,79 @@
+use super::{login_as, new_proxy, profile, Output};
+use crate::{audio::AlbumDownloader, Id};
+
+use anyhow::Result;
+use colored::*;
+use serde_json::json;
+use std::{env, path::PathBuf};
+
+pub fn handle_album(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("info", matches)) => {
+            let proxy = new_proxy()?;
+            let album_id = *matches.get_one::<Id>("ALBUM").unwrap();
+            let album = AlbumDownloader::new(&proxy).album(album_id)?;
+            Output::of(matches).print(&album, |album| {
+                println!("{} ({})", album.name.bold(), album.id);
+                println!(
+                    "{}  novel {}  {}",
+                    album.author.as_deref().unwrap_or_default(),
+                    album.novel_id,
+                    album.tags.join(" ")
+                );
+                for track in &album.tracks {
+                    println!(
+                        "  {:>3}  {} ({}){}",
+                        track.order,
+                        track.title,
+                        track.id,
+                        if track.is_free { "" } else { "  VIP" }
+                    );
+                }
+            })?;
+        }
+        Some(("download", matches)) => {
+            let proxy = match matches.get_one::<String>("user") {
+                Some(user) => login_as(Some(user))?,
+                None => login_as(None).or_else(|_| new_proxy())?,
+            };
+            let album_id = *matches.get_one::<Id>("ALBUM").unwrap();
+            let dir = match (matches.get_one::<String>("out"), &profile().download_dir) {
+                (Some(dir), _) => PathBuf::from(dir),
+                (None, Some(dir)) => dir.to_owned(),
+                (None, None) => env::current_dir()?,
+            };
+            let downloader = AlbumDownloader::new(&proxy).with_tags(!matches.get_flag("no-tags"));
+            let album = downloader.album(album_id)?;
+            let result = downloader.download(&album, dir)?;
+            for track in &result.skipped {
+                eprintln!(
+                    "{}: skipped {} ({}): {}",
+                    "Warning".bold().yellow(),
+                    track.title,
+                    track.id,
+                    track.reason
+                );
+            }
+            let value = json!({
+                "album_id": album_id,
+                "name": album.name,
+                "tracks": result.saved,
+                "skipped": result.skipped,
+            });
+            Output::of(matches).print(&value, |_| {
+                for track in &result.saved {
+                    println!("Saved {}", track.path.display());
+                }
+                println!(
+                    "Saved {} of {} tracks of {}",
+                    result.saved.len(),
+                    album.tracks.len(),
+                    album.name.bold()
+                );
+            })?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use super::{format_date, login_as, new_proxy, Output};
+use crate::{Author, Id};
+
+use anyhow::{bail, Result};
+use colored::*;
+
+pub fn handle_author(matches: &clap::ArgMatches) -> Result<()> {
+    let author = match matches.get_one::<Id>("USER_ID") {
+        Some(user_id) => {
+            let proxy = match matches.get_one::<String>("user") {
+                Some(user) => login_as(Some(user))?,
+                None => new_proxy()?,
+            };
+            let profile = proxy.user_info(*user_id)?.parse()?;
+            let mut novels = vec![];
+            for novel in proxy.novels_of(*user_id)? {
+                novels.push(novel.parse()?);
+            }
+            Author::new(profile, novels)
+        }
+        None => {
+            let proxy = login_as(matches.get_one::<String>("user"))?;
+            let private = proxy.profile()?;
+            if !private.isAuthor {
+                bail!("{} is not an author", private.nickName);
+            }
+            let profile = proxy.user_info(private.accountId)?.parse()?;
+            let mut novels = vec![];
+            for novel in proxy.novels_of(private.accountId)? {
+                novels.push(novel.parse()?);
+            }
+            let mut announcements = vec![];
+            for announcement in proxy.all_author_announcements()? {
+                announcements.push(announcement.parse()?);
+            }
+            let info = proxy.author_info()?;
+            Author::new(profile, novels).with_private(info.authorName, announcements)
+        }
+    };
+
+    Output::of(matches).print(&author, |author| {
+        let profile = &author.profile;
+        match &author.pen_name {
+            Some(pen_name) => println!("{} ({}) {pen_name}", profile.nickname.bold(), profile.id),
+            None => println!("{} ({})", profile.nickname.bold(), profile.id),
+        }
+        println!(
+            "  Fans: {}, follows: {}",
+            profile.total_fans, profile.total_follows
+        );
+        let stats = &author.stats;
+        println!(
+            "  Novels: {} ({} finished), {} characters",
+            stats.total_novels, stats.finished_novels, stats.total_chars
+        );
+        println!(
+            "  Views: {}, likes: {}, favorites: {}, tickets: {}",
+            stats.total_views, stats.total_likes, stats.total_favorites, stats.total_tickets
+        );
+        if !profile.intro.is_empty() {
+            println!();
+            println!("{}", profile.intro);
+        }
+        println!();
+        for novel in &author.novels {
+            println!(
+                "{} ({})  {} characters  {}{}",
+                novel.name.bold(),
+                novel.id,
+                novel.total_chars,
+                format_date(novel.last_update_time),
+                if novel.is_finished { "  finished" } else { "" }
+            );
+        }
+        if !author.announcements.is_empty() {
+            println!();
+            for announcement in &author.announcements {
+                println!(
+                    "{}  {}",
+                    announcement.title.bold(),
+                    format_date(announcement.creation_time).dimmed()
+                );
+                println!("{}", announcement.content);
+            }
+        }
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use super::{confirm, login_as, Output};
+use crate::{Chapter, PurchasePlan};
+
+use anyhow::{bail, Result};
+use colored::*;
+use serde::Serialize;
+
+/// 购买报告
+#[derive(Debug, Serialize)]
+struct PurchaseReport<'a> {
+    plan: &'a PurchasePlan,
+    total: usize,        //<! 总价
+    origin_total: usize, //<! 总原价
+    bought: bool,        //<! 是否已购买
+}
+
+impl<'a> PurchaseReport<'a> {
+    fn new(plan: &'a PurchasePlan, bought: bool) -> Self {
+        Self {
+            plan,
+            total: plan.total(),
+            origin_total: plan.origin_total(),
+            bought,
+        }
+    }
+}
+
+pub fn handle_buy(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = login_as(matches.get_one::<String>("user"))?;
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+    let budget = *matches.get_one::<usize>("budget").unwrap();
+
+    let chapters: Vec<Chapter> = proxy
+        .catalogue_of(novel_id)?
+        .parse()?
+        .into_iter()
+        .flat_map(|e| e.chapters)
+        .collect();
+
+    let chapters: Vec<Chapter> = if matches.get_flag("all") {
+        chapters
+    } else if let Some(range) = matches.get_one::<String>("range") {
+        let Some((first, last)) = range.split_once(':') else {
+            bail!("invalid chapter range: {range}");
+        };
+        let (first, last) = (first.parse::<i32>()?, last.parse::<i32>()?);
+        let Some(start) = chapters.iter().position(|e| e.id == first) else {
+            bail!("unknown chapter: {first}");
+        };
+        let Some(end) = chapters.iter().position(|e| e.id == last) else {
+            bail!("unknown chapter: {last}");
+        };
+        if start > end {
+            bail!("invalid chapter range: {range}");
+        }
+        chapters
+            .into_iter()
+            .skip(start)
+            .take(end - start + 1)
+            .collect()
+    } else {
+        let ids: Vec<i32> = matches
+            .get_many::<i32>("chapter")
+            .unwrap()
+            .copied()
+            .collect();
+        if let Some(id) = ids.iter().find(|id| !chapters.iter().any(|e| e.id == **id)) {
+            bail!("unknown chapter: {id}");
+        }
+        chapters
+            .into_iter()
+            .filter(|e| ids.contains(&e.id))
+            .collect()
+    };
+
+    let profile = proxy.profile()?.parse(proxy.money()?)?;
+    let plan = PurchasePlan::new(novel_id, &chapters);
+    let output = Output::of(matches);
+    if plan.items.is_empty() {
+        return output.print(&PurchaseReport::new(&plan, false), |_| {
+            println!("Nothing to buy, all the chapters are unlocked")
+        });
+    }
+
+    if output.is_table() {
+        print_plan(&plan);
+    }
+
+    let balance = profile.banlance + profile.vouchers;
+    if let Err(err) = plan.check(budget, balance) {
+        let hint = "Purchase refused";
+        eprintln!("{}: {err}", hint.bold().red());
+        bail!(hint.to_lowercase());
+    }
+
+    let dry_run = matches.get_flag("dry-run");
+    if !dry_run
+        && !matches.get_flag("yes")
+        && !confirm(&format!(
+            "buy {} chapters of {novel_id} for {} fire coins?",
+            plan.items.len(),
+            plan.total()
+        ))?
+    {
+        return output.print(&PurchaseReport::new(&plan, false), |_| ());
+    }
+    if !dry_run {
+        let ids: Vec<i32> = plan.items.iter().map(|e| e.chapter_id).collect();
+        for chunk in ids.chunks(50) {
+            proxy.order_chapters(novel_id, chunk)?;
+        }
+    }
+
+    output.print(&PurchaseReport::new(&plan, !dry_run), |_| {
+        if !dry_run {
+            println!(
+                "Bought {} chapters of {}",
+                plan.items.len(),
+                novel_id.to_string().bold()
+            );
+        }
+    })
+}
+
+fn print_plan(plan: &PurchasePlan) {
+    for item in &plan.items {
+        println!(
+            "{:>10}  {}  {} (origin {})",
+            item.chapter_id,
+            item.title,
+            item.price.to_string().yellow(),
+            item.origin_price,
+        );
+    }
+    println!(
+        "{}: {} chapters, {} fire coins (origin {})",
+        "Total".bold(),
+        plan.items.len(),
+        plan.total().to_string().yellow(),
+        plan.origin_total(),
+    );
+}
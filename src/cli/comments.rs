@@ -0,0 +1,117 @@
+use super::{format_date, new_proxy, Output};
+use crate::{Comment, Reply, Thread};
+
+use anyhow::Result;
+use colored::*;
+
+/// 获取评论或回复时最多请求的页数，避免服务端持续返回满页时无限请求
+const MAX_COMMENT_PAGES: usize = 100;
+
+pub fn handle_comments(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = new_proxy()?;
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+    let limit = *matches.get_one::<usize>("limit").unwrap();
+    let is_long = matches.get_flag("long");
+    let size = 20;
+
+    let mut comments: Vec<Comment> = vec![];
+    let mut truncated = true;
+    for page in 0..MAX_COMMENT_PAGES {
+        let batch = if is_long {
+            let sort = matches
+                .get_one::<String>("sort")
+                .map_or("addtime", String::as_str);
+            let charlen = *matches.get_one::<usize>("charlen").unwrap();
+            proxy
+                .long_comments_of(novel_id, sort, charlen, page, size)?
+                .into_iter()
+                .map(|e| e.parse())
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            let sort = matches
+                .get_one::<String>("sort")
+                .map_or("smart", String::as_str);
+            let r#type = matches.get_one::<String>("type").unwrap();
+            proxy
+                .comments_of(novel_id, r#type, sort, page, size)?
+                .into_iter()
+                .map(|e| e.parse())
+                .collect::<Result<Vec<_>>>()?
+        };
+        let done = batch.len() < size;
+        comments.extend(batch);
+        if done || comments.len() >= limit {
+            truncated = false;
+            break;
+        }
+    }
+    if truncated {
+        eprintln!(
+            "{}: comments are truncated to the first {} pages",
+            "Warning".bold().yellow(),
+            MAX_COMMENT_PAGES
+        );
+    }
+    comments.truncate(limit);
+
+    let mut replies: Vec<Reply> = vec![];
+    for comment in comments.iter().filter(|e| e.total_replies > 0) {
+        let mut truncated = true;
+        for page in 0..MAX_COMMENT_PAGES {
+            let batch = if is_long {
+                proxy.long_comment_replies(comment.id, page, size)?
+            } else {
+                proxy.comment_replies(comment.id, page, size)?
+            };
+            let done = batch.len() < size;
+            for reply in batch {
+                replies.push(reply.parse()?);
+            }
+            if done {
+                truncated = false;
+                break;
+            }
+        }
+        if truncated {
+            eprintln!(
+                "{}: replies to comment {} are truncated to the first {} pages",
+                "Warning".bold().yellow(),
+                comment.id,
+                MAX_COMMENT_PAGES
+            );
+        }
+    }
+
+    let threads = Thread::stitch(comments, replies);
+    let output = match matches.get_flag("json") {
+        true => Output::Json,
+        false => Output::of(matches),
+    };
+    output.print(threads.as_slice(), print_threads)
+}
+
+fn print_threads(threads: &[Thread]) {
+    for thread in threads {
+        let comment = &thread.comment;
+        println!(
+            "{} {} {} likes{}",
+            comment.nickname.bold(),
+            format_date(comment.creation_time).dimmed(),
+            comment.total_likes,
+            if comment.is_sticky { " [sticky]" } else { "" },
+        );
+        if let Some(title) = &comment.title {
+            println!("{}", title.bold());
+        }
+        println!("{}", comment.content);
+        for reply in &thread.replies {
+            println!(
+                "    {} {} {}",
+                reply.nickname.bold(),
+                format_date(reply.creation_time).dimmed(),
+                reply.content,
+            );
+        }
+        println!();
+    }
+}
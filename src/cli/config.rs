@@ -0,0 +1,93 @@
+use super::Output;
+use crate::{
+    local::{BookStore, Config, Profile},
+    Proxy,
+};
+
+use anyhow::Result;
+use colored::*;
+use std::{env, sync::OnceLock};
+
+static PROFILE: OnceLock<Profile> = OnceLock::new();
+
+/// 选中的配置档名，依次取自 `--profile`、环境变量 `SFUTILS_PROFILE`，默认为 `default`
+fn profile_name(matches: &clap::ArgMatches) -> String {
+    match matches.get_one::<String>("profile") {
+        Some(name) => name.to_owned(),
+        None => env::var("SFUTILS_PROFILE").unwrap_or_else(|_| "default".to_string()),
+    }
+}
+
+/// 加载选中的配置档并应用环境变量覆盖，需在执行子命令前调用
+pub fn init_profile(matches: &clap::ArgMatches) -> Result<()> {
+    let profile = Config::open()?.profile(&profile_name(matches)).with_env();
+    let _ = PROFILE.set(profile);
+    Ok(())
+}
+
+/// 当前生效的配置档
+pub fn profile() -> &'static Profile {
+    PROFILE.get_or_init(Profile::default)
+}
+
+/// 打开当前配置档的下载目录，未设置 `download_dir` 时使用章节缓存目录
+pub fn book_store() -> Result<BookStore> {
+    match &profile().download_dir {
+        Some(dir) => BookStore::open_at(dir.to_owned()),
+        None => BookStore::open(),
+    }
+}
+
+/// 按当前配置档创建客户端
+pub fn new_proxy() -> Result<Proxy> {
+    let profile = profile();
+    let mut builder = Proxy::builder();
+    if let Some(version) = &profile.app_version {
+        builder = builder.with_app_version(version);
+    }
+    if let Some(channel) = &profile.channel {
+        builder = builder.with_channel(channel);
+    }
+    if let Some(url) = &profile.proxy {
+        builder = builder.with_http_proxy(url);
+    }
+    builder.build()
+}
+
+pub fn handle_config(matches: &clap::ArgMatches) -> Result<()> {
+    let name = profile_name(matches);
+    let mut config = Config::open()?;
+
+    match matches.subcommand() {
+        Some(("list", matches)) => {
+            Output::of(matches).print(&config.profiles, |profiles| {
+                for (name, profile) in profiles {
+                    println!("[{}]", name.bold());
+                    for key in Profile::KEYS {
+                        if let Ok(Some(value)) = profile.get(key) {
+                            println!("{key} = {value}");
+                        }
+                    }
+                }
+            })?;
+        }
+        Some(("get", matches)) => {
+            let key = matches.get_one::<String>("KEY").unwrap();
+            let value = config.profile(&name).get(key)?;
+            Output::of(matches).print(&value, |value| {
+                if let Some(value) = value {
+                    println!("{value}");
+                }
+            })?;
+        }
+        Some(("set", matches)) => {
+            let key = matches.get_one::<String>("KEY").unwrap();
+            let value = matches.get_one::<String>("VALUE").map(String::as_str);
+            config.profile_mut(&name).set(key, value)?;
+            config.save()?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,475 @@
+use super::{
+    book_store, check, format_date, get_authenticated_users, is_signed_today, login_as, new_proxy,
+    notify_all, profile, refresh_auth, Output,
+};
+use crate::{
+    download::Downloader,
+    local::{append_daemon_log, Config, DaemonState, ShelfSnapshot, Task, TaskKind, TaskRecord},
+    notify::{CommandSink, JsonlSink, Sink, WebhookSink},
+    Id, Timestamp, Value,
+};
+
+use anyhow::{bail, Result};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use colored::*;
+use rand::Rng;
+use serde_json::json;
+use std::{collections::HashMap, process, thread, time::Duration};
+
+/// 失败后第一次重试的等待秒数，之后每次失败翻倍
+const RETRY_DELAY: Timestamp = 5 * 60;
+
+/// 签到任务重试等待的上限
+const MAX_SIGN_IN_RETRY_DELAY: Timestamp = 6 * 60 * 60;
+
+/// 任务失败后距下一次重试的秒数，成功时返回 `None`
+///
+/// 等待时间随连续失败的次数翻倍，不超过任务的运行间隔
+fn retry_delay(task: &Task, record: &TaskRecord) -> Result<Option<Timestamp>> {
+    if record.ok {
+        return Ok(None);
+    }
+    let cap = match task.kind {
+        TaskKind::SignIn => MAX_SIGN_IN_RETRY_DELAY,
+        _ => task.interval()?,
+    };
+    let failures = record.failures.clamp(1, 16);
+    Ok(Some((RETRY_DELAY << (failures - 1)).min(cap)))
+}
+
+/// 任务是否到期，签到任务每天运行一次，其它任务按间隔运行，失败的任务按退避时间重试
+fn is_due(task: &Task, record: Option<&TaskRecord>, now: Timestamp) -> Result<bool> {
+    let Some(record) = record else {
+        return Ok(true);
+    };
+    if let Some(delay) = retry_delay(task, record)? {
+        return Ok(now - record.last_run >= delay);
+    }
+    Ok(match task.kind {
+        TaskKind::SignIn => local_date(record.last_run) != local_date(now),
+        _ => now - record.last_run >= task.interval()?,
+    })
+}
+
+/// 任务的下一次到期时间
+fn next_run(task: &Task, record: Option<&TaskRecord>) -> Result<Option<Timestamp>> {
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    if let Some(delay) = retry_delay(task, record)? {
+        return Ok(Some(record.last_run + delay));
+    }
+    Ok(match task.kind {
+        TaskKind::SignIn => local_date(record.last_run)
+            .and_then(|e| e.succ_opt())
+            .and_then(|e| e.and_hms_opt(0, 0, 0))
+            .and_then(|e| Local.from_local_datetime(&e).earliest())
+            .map(|e| e.timestamp()),
+        _ => Some(record.last_run + task.interval()?),
+    })
+}
+
+fn local_date(timestamp: Timestamp) -> Option<chrono::NaiveDate> {
+    NaiveDateTime::from_timestamp_opt(timestamp, 0)
+        .map(|e| Local.from_utc_datetime(&e).date_naive())
+}
+
+fn accounts_of(task: &Task) -> Result<Vec<String>> {
+    if task.accounts.is_empty() {
+        get_authenticated_users()
+    } else {
+        Ok(task.accounts.clone())
+    }
+}
+
+/// 汇总各账号的执行结果，存在失败时返回错误
+fn summarize(results: Vec<(String, Result<String>)>) -> Result<String> {
+    if results.is_empty() {
+        return Ok("no accounts".to_string());
+    }
+    let failed = results.iter().any(|(_, e)| e.is_err());
+    let message = results
+        .into_iter()
+        .map(|(account, result)| match result {
+            Ok(message) => format!("{account}: {message}"),
+            Err(err) => format!("{account}: {err}"),
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    if failed {
+        bail!(message);
+    }
+    Ok(message)
+}
+
+fn sign_in(task: &Task) -> Result<String> {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let results = accounts_of(task)?
+        .into_iter()
+        .map(|account| {
+            let result = login_as(Some(&account)).and_then(|proxy| {
+                if is_signed_today(&proxy.sign_info()?) {
+                    return Ok("already signed".to_string());
+                }
+                proxy.sign_in(&date)?;
+                Ok("signed".to_string())
+            });
+            (account, result)
+        })
+        .collect();
+    summarize(results)
+}
+
+fn refresh(task: &Task) -> Result<String> {
+    let results = accounts_of(task)?
+        .into_iter()
+        .map(|account| {
+            let result = refresh_auth(&account).map(|e| format!("expires at {}", format_date(e)));
+            (account, result)
+        })
+        .collect();
+    summarize(results)
+}
+
+/// 任务配置的通知方式
+fn sinks_of(task: &Task) -> Vec<Box<dyn Sink>> {
+    let mut sinks: Vec<Box<dyn Sink>> = vec![];
+    for command in &task.exec {
+        sinks.push(Box::new(CommandSink {
+            command: command.to_owned(),
+        }));
+    }
+    for url in &task.webhooks {
+        sinks.push(Box::new(WebhookSink::new(url)));
+    }
+    if let Some(path) = &task.log {
+        sinks.push(Box::new(JsonlSink {
+            path: path.to_owned(),
+        }));
+    }
+    sinks
+}
+
+/// 检查书架中小说的更新，与 `watch` 共用快照并发送到任务配置的通知方式
+fn poll(task: &Task) -> Result<String> {
+    let mut sinks = sinks_of(task);
+    let results = accounts_of(task)?
+        .into_iter()
+        .map(|account| {
+            let result = ShelfSnapshot::open(&account).and_then(|mut snapshot| {
+                let updates = check(Some(&account), &mut snapshot)?;
+                notify_all(&mut sinks, &updates);
+                snapshot.save()?;
+                Ok(updates)
+            });
+            let result = result.map(|updates| {
+                if updates.is_empty() {
+                    return "no updates".to_string();
+                }
+                let names: Vec<&str> = updates.iter().map(|e| e.novel_name.as_str()).collect();
+                format!("{} updated: {}", updates.len(), names.join(", "))
+            });
+            (account, result)
+        })
+        .collect();
+    summarize(results)
+}
+
+/// 增量下载小说到下载目录
+fn download(task: &Task) -> Result<String> {
+    let proxy = match task.accounts.first() {
+        Some(account) => login_as(Some(account))?,
+        None => login_as(None).or_else(|_| new_proxy())?,
+    };
+    let novels: Vec<Id> = if task.novels.is_empty() {
+        let mut novels = vec![];
+        for pocket in proxy.favoirtes()? {
+            let refs = pocket.expand.and_then(|e| e.novels).unwrap_or_default();
+            novels.extend(refs.into_iter().map(|e| e.novelId));
+        }
+        novels
+    } else {
+        task.novels.clone()
+    };
+
+    // 单本小说下载失败时记录日志并继续下载其它小说，全部失败时任务失败
+    let store = book_store()?;
+    let downloader = Downloader::new(&proxy).with_concurrency(profile().concurrency());
+    let mut total = 0;
+    let mut failed = vec![];
+    for novel_id in &novels {
+        match downloader.download(&store, *novel_id) {
+            Ok(count) => total += count,
+            Err(err) => {
+                log(&task.name(), &Err(err.context(format!("novel {novel_id}"))));
+                failed.push(*novel_id);
+            }
+        }
+    }
+    let message = format!(
+        "downloaded {total} chapters of {} novels",
+        novels.len() - failed.len()
+    );
+    if failed.is_empty() {
+        return Ok(message);
+    }
+    let failed: Vec<String> = failed.iter().map(|e| e.to_string()).collect();
+    let message = format!("{message}, failed: {}", failed.join(", "));
+    if failed.len() == novels.len() {
+        bail!(message);
+    }
+    Ok(message)
+}
+
+fn run_task(task: &Task) -> Result<String> {
+    match task.kind {
+        TaskKind::SignIn => sign_in(task),
+        TaskKind::Refresh => refresh(task),
+        TaskKind::Poll => poll(task),
+        TaskKind::Download => download(task),
+    }
+}
+
+fn log(name: &str, result: &Result<String>) {
+    let time = Local::now().format("%Y-%m-%d %H:%M:%S");
+    let line = match result {
+        Ok(message) => format!("{time} [{name}] ok: {message}"),
+        Err(err) => format!("{time} [{name}] failed: {err}"),
+    };
+    println!("{line}");
+    if let Err(err) = append_daemon_log(&line) {
+        eprintln!("{}: {err}", "Failed to write log".bold().red());
+    }
+}
+
+/// 进程是否仍在运行，信号 0 只检查进程是否存在，`EPERM` 表示进程属于其他用户
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    let alive = unsafe { libc::kill(pid as libc::pid_t, 0) == 0 };
+    alive || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// 非 Unix 平台不检查记录的进程是否仍在运行
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    false
+}
+
+fn run(once: bool) -> Result<()> {
+    let config = Config::open()?.daemon;
+    if config.tasks.is_empty() {
+        bail!("no tasks scheduled in the config file");
+    }
+    for task in &config.tasks {
+        task.interval()?;
+    }
+
+    let mut state = DaemonState::open()?;
+    if let Some(pid) = state.pid.filter(|e| *e != process::id()) {
+        if is_running(pid) {
+            bail!("another daemon is already running (pid {pid})");
+        }
+    }
+    state.pid = Some(process::id());
+    state.started = Some(Utc::now().timestamp());
+    state.save()?;
+
+    let mut rng = rand::thread_rng();
+    let mut pending: HashMap<String, Timestamp> = HashMap::new();
+    loop {
+        let now = Utc::now().timestamp();
+        for task in &config.tasks {
+            let name = task.name();
+            if !is_due(task, state.tasks.get(&name), now)? {
+                continue;
+            }
+            let at = *pending
+                .entry(name.to_owned())
+                .or_insert_with(|| now + rng.gen_range(0..=config.jitter) as Timestamp);
+            if at > now && !once {
+                continue;
+            }
+            pending.remove(&name);
+
+            let result = run_task(task);
+            log(&name, &result);
+            state.record(&name, Utc::now().timestamp(), &result);
+            state.save()?;
+        }
+        if once {
+            break;
+        }
+        thread::sleep(Duration::from_secs(30));
+    }
+
+    state.pid = None;
+    state.save()
+}
+
+pub fn handle_daemon(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("run", matches)) => run(matches.get_flag("once")),
+        Some(("status", matches)) => {
+            let config = Config::open()?.daemon;
+            let state = DaemonState::open()?;
+            let mut tasks: Vec<Value> = vec![];
+            for task in &config.tasks {
+                let name = task.name();
+                let record = state.tasks.get(&name);
+                tasks.push(json!({
+                    "name": name,
+                    "kind": task.kind,
+                    "last_run": record.map(|e| e.last_run),
+                    "next_run": next_run(task, record)?,
+                    "ok": record.map(|e| e.ok),
+                    "message": record.map(|e| e.message.to_owned()),
+                }));
+            }
+            let status = json!({ "pid": state.pid.filter(|e| is_running(*e)), "started": state.started, "tasks": tasks });
+
+            Output::of(matches).print(&status, |_| {
+                if let Some(started) = state.started {
+                    match state.pid.filter(|e| is_running(*e)) {
+                        Some(pid) => println!("Running since {} (pid {pid})", format_time(started)),
+                        None => println!("Last started at {}", format_time(started)),
+                    }
+                }
+                for task in &tasks {
+                    let name = task["name"].as_str().unwrap();
+                    let last_run = task["last_run"]
+                        .as_i64()
+                        .map_or("never".to_string(), format_time);
+                    let next_run = task["next_run"]
+                        .as_i64()
+                        .map_or("now".to_string(), format_time);
+                    let result = match task["ok"].as_bool() {
+                        Some(true) => "ok".green(),
+                        Some(false) => "failed".red(),
+                        None => "-".normal(),
+                    };
+                    println!(
+                        "{}  {result}  last {last_run}  next {next_run}",
+                        name.bold()
+                    );
+                    if let Some(message) = task["message"].as_str() {
+                        println!("    {message}");
+                    }
+                }
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn format_time(timestamp: Timestamp) -> String {
+    NaiveDateTime::from_timestamp_opt(timestamp, 0)
+        .map(|e| {
+            Local
+                .from_utc_datetime(&e)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(kind: &str, interval: Option<&str>) -> Task {
+        serde_json::from_value(json!({ "kind": kind, "interval": interval })).unwrap()
+    }
+
+    fn record(last_run: Timestamp, ok: bool, failures: u32) -> TaskRecord {
+        TaskRecord {
+            last_run,
+            ok,
+            message: String::new(),
+            failures,
+        }
+    }
+
+    #[test]
+    fn interval_tasks_are_due_after_their_interval() {
+        let task = task("poll", Some("1h"));
+        let now = 1_700_000_000;
+        assert!(is_due(&task, None, now).unwrap());
+        assert_eq!(next_run(&task, None).unwrap(), None);
+
+        let done = record(now, true, 0);
+        assert!(!is_due(&task, Some(&done), now + 3599).unwrap());
+        assert!(is_due(&task, Some(&done), now + 3600).unwrap());
+        assert_eq!(next_run(&task, Some(&done)).unwrap(), Some(now + 3600));
+    }
+
+    #[test]
+    fn sign_in_is_due_once_per_local_day() {
+        let task = task("sign-in", None);
+        let morning = Local
+            .with_ymd_and_hms(2024, 5, 20, 9, 0, 0)
+            .unwrap()
+            .timestamp();
+        let midnight = Local
+            .with_ymd_and_hms(2024, 5, 21, 0, 0, 0)
+            .unwrap()
+            .timestamp();
+        let done = record(morning, true, 0);
+        assert!(!is_due(&task, Some(&done), midnight - 1).unwrap());
+        assert!(is_due(&task, Some(&done), midnight).unwrap());
+        assert_eq!(next_run(&task, Some(&done)).unwrap(), Some(midnight));
+    }
+
+    #[test]
+    fn failed_tasks_back_off_exponentially() {
+        let task = task("poll", Some("1h"));
+        let delays: Vec<Timestamp> = (1..=5)
+            .map(|failures| {
+                retry_delay(&task, &record(0, false, failures))
+                    .unwrap()
+                    .unwrap()
+            })
+            .collect();
+        // 5、10、20、40 分钟，之后不超过运行间隔
+        assert_eq!(delays, [300, 600, 1200, 2400, 3600]);
+        assert_eq!(retry_delay(&task, &record(0, true, 0)).unwrap(), None);
+
+        let failed = record(1_700_000_000, false, 2);
+        assert!(!is_due(&task, Some(&failed), 1_700_000_000 + 599).unwrap());
+        assert!(is_due(&task, Some(&failed), 1_700_000_000 + 600).unwrap());
+
+        let sign_in = self::task("sign-in", None);
+        let delay = retry_delay(&sign_in, &record(0, false, 16)).unwrap();
+        assert_eq!(delay, Some(MAX_SIGN_IN_RETRY_DELAY));
+    }
+
+    #[test]
+    fn persisted_state_survives_a_restart() {
+        let task = task("download", Some("6h"));
+        let path = std::env::temp_dir().join(format!("sfutils-daemon-{}.json", std::process::id()));
+        let now = 1_700_000_000;
+
+        let mut state = DaemonState::open_at(path.clone()).unwrap();
+        state.record("download", now, &Err(anyhow::anyhow!("offline")));
+        state.record("download", now + 300, &Err(anyhow::anyhow!("offline")));
+        state.save().unwrap();
+
+        // 重启后保留连续失败的次数，按退避时间重试
+        let state = DaemonState::open_at(path.clone()).unwrap();
+        let failed = state.tasks.get("download");
+        assert_eq!(failed.unwrap().failures, 2);
+        assert!(!is_due(&task, failed, now + 600).unwrap());
+        assert!(is_due(&task, failed, now + 900).unwrap());
+
+        // 运行并保存后立即重启不会再次运行
+        let mut state = state;
+        state.record("download", now + 900, &Ok("done".to_string()));
+        state.save().unwrap();
+        let state = DaemonState::open_at(path.clone()).unwrap();
+        let done = state.tasks.get("download");
+        assert_eq!(done.unwrap().failures, 0);
+        assert!(!is_due(&task, done, now + 900).unwrap());
+        assert!(!is_due(&task, done, now + 900 + 6 * 3600 - 1).unwrap());
+        assert!(is_due(&task, done, now + 900 + 6 * 3600).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,316 @@
+use super::{
+    format_date, get_active_user, get_authenticated_users, get_expires_of, is_signed_today,
+    login_as, refresh_auth, set_active_user,
+    term::{with_terminal, Term},
+    Output,
+};
+use crate::{to_unix_timestamp, Proxy, Timestamp, Value};
+
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use serde::Serialize;
+use serde_json::json;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+/// 账号概况
+#[derive(Debug, Serialize)]
+struct Summary {
+    vip_level: usize,
+    vip_point: usize,
+    point_of_next_level: usize,
+    banlance: usize,
+    vouchers: usize,
+    streak: i32,        //<! 连续签到的天数
+    signed_today: bool, //<! 今日是否已签到
+    shelf: usize,       //<! 书架中的小说数量
+    updated: usize,     //<! 书架中最近一天内更新的小说数量
+}
+
+impl Summary {
+    fn fetch(proxy: &Proxy) -> Result<Self> {
+        let profile = proxy.profile()?.parse(proxy.money()?)?;
+
+        let signs = proxy.sign_info()?;
+        let latest = signs.iter().max_by_key(|e| (e.year, e.month, e.day));
+        let signed_today = is_signed_today(&signs);
+
+        let since = Utc::now().timestamp() - 24 * 60 * 60;
+        let mut shelf = 0;
+        let mut updated = 0;
+        for pocket in proxy.favoirtes()? {
+            let Some(novels) = pocket.expand.and_then(|e| e.novels) else {
+                continue;
+            };
+            shelf += novels.len();
+            updated += novels
+                .iter()
+                .filter(|e| to_unix_timestamp(&e.lastUpdateTime).is_ok_and(|t| t >= since))
+                .count();
+        }
+
+        Ok(Self {
+            vip_level: profile.vip.level,
+            vip_point: profile.vip.point,
+            point_of_next_level: profile.vip.point_of_next_level,
+            banlance: profile.banlance,
+            vouchers: profile.vouchers,
+            streak: latest.map_or(0, |e| e.continueNum),
+            signed_today,
+            shelf,
+            updated,
+        })
+    }
+}
+
+struct Account {
+    nickname: String,
+    expires: Option<Timestamp>, //<! 本地凭证的过期时间
+    proxy: Option<Proxy>,
+    summary: Option<Result<Summary, String>>, //<! 账号概况，尚未登录获取时为 None
+}
+
+impl Account {
+    fn new(nickname: String) -> Self {
+        Self {
+            expires: get_expires_of(&nickname),
+            nickname,
+            proxy: None,
+            summary: None,
+        }
+    }
+
+    /// 登录并获取账号概况
+    fn load(&mut self) {
+        self.expires = get_expires_of(&self.nickname);
+        match login_as(Some(&self.nickname))
+            .and_then(|proxy| Summary::fetch(&proxy).map(|summary| (proxy, summary)))
+        {
+            Ok((proxy, summary)) => {
+                self.proxy = Some(proxy);
+                self.summary = Some(Ok(summary));
+            }
+            Err(err) => {
+                self.proxy = None;
+                self.summary = Some(Err(err.to_string()));
+            }
+        }
+    }
+
+    fn row(&self, active: bool, now: Timestamp) -> Row<'_> {
+        let expires = match self.expires {
+            Some(expires) if expires <= now => {
+                Cell::from(format_date(expires)).style(Style::default().fg(Color::Red))
+            }
+            Some(expires) => Cell::from(format_date(expires)),
+            None => Cell::from("-"),
+        };
+        let mut cells = vec![
+            Cell::from(if active { "*" } else { "" }),
+            Cell::from(self.nickname.as_str()),
+            expires,
+        ];
+        match &self.summary {
+            Some(Ok(e)) => cells.extend([
+                Cell::from(e.vip_level.to_string()),
+                Cell::from(format!("{}/{}", e.vip_point, e.point_of_next_level)),
+                Cell::from(e.banlance.to_string()).style(Style::default().fg(Color::Yellow)),
+                Cell::from(e.vouchers.to_string()),
+                Cell::from(e.streak.to_string()),
+                if e.signed_today {
+                    Cell::from("yes")
+                } else {
+                    Cell::from("no").style(Style::default().fg(Color::Red))
+                },
+                Cell::from(e.shelf.to_string()),
+                Cell::from(e.updated.to_string()),
+            ]),
+            Some(Err(err)) => {
+                cells.push(Cell::from(err.as_str()).style(Style::default().fg(Color::Red)))
+            }
+            None => cells.push(Cell::from("-")),
+        }
+        Row::new(cells)
+    }
+}
+
+struct Dashboard {
+    accounts: Vec<Account>,
+    active: Option<String>,
+    table: TableState,
+    status: String,
+}
+
+impl Dashboard {
+    /// 账号在被选中时才登录获取概况，避免启动时依次登录所有账号
+    fn new() -> Result<Self> {
+        let accounts: Vec<Account> = get_authenticated_users()?
+            .into_iter()
+            .map(Account::new)
+            .collect();
+        let mut table = TableState::default();
+        if !accounts.is_empty() {
+            table.select(Some(0));
+        }
+        Ok(Self {
+            accounts,
+            active: get_active_user(),
+            table,
+            status: String::new(),
+        })
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.table.selected().filter(|e| *e < self.accounts.len())
+    }
+
+    /// 选中的账号尚未获取概况时返回其下标
+    fn pending(&self) -> Option<usize> {
+        self.selected()
+            .filter(|e| self.accounts[*e].summary.is_none())
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if let Some(index) = self.selected() {
+            let last = self.accounts.len() as isize - 1;
+            let index = (index as isize + delta).clamp(0, last);
+            self.table.select(Some(index as usize));
+        }
+    }
+
+    fn refresh(&mut self) {
+        let Some(index) = self.selected() else {
+            return;
+        };
+        let nickname = self.accounts[index].nickname.to_owned();
+        self.status = match refresh_auth(&nickname) {
+            Ok(expires) => format!("Refreshed {nickname}, expires at {}", format_date(expires)),
+            Err(err) => format!("Refresh failed: {nickname}: {err}"),
+        };
+        self.accounts[index].load();
+    }
+
+    fn switch(&mut self) {
+        let Some(index) = self.selected() else {
+            return;
+        };
+        let nickname = self.accounts[index].nickname.to_owned();
+        self.status = match set_active_user(&nickname) {
+            Ok(_) => format!("Switched to {nickname}"),
+            Err(err) => format!("Switch failed: {err}"),
+        };
+        self.active = get_active_user();
+    }
+
+    /// 处理按键，返回是否退出
+    fn handle(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Char('r') => self.refresh(),
+            KeyCode::Enter | KeyCode::Char('a') => self.switch(),
+            _ => (),
+        }
+        false
+    }
+
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(f.size());
+
+        let now = Utc::now().timestamp();
+        let header = Row::new(vec![
+            "", "Nickname", "Expires", "VIP", "Points", "Balance", "Vouchers", "Streak", "Signed",
+            "Shelf", "Updated",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Min(12),
+            Constraint::Length(10),
+            Constraint::Length(3),
+            Constraint::Length(11),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(5),
+            Constraint::Length(7),
+        ];
+        let table = Table::new(
+            self.accounts
+                .iter()
+                .map(|e| e.row(self.active.as_ref() == Some(&e.nickname), now)),
+        )
+        .header(header)
+        .block(Block::default().title("Accounts").borders(Borders::ALL))
+        .widths(&widths)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(table, rows[0], &mut self.table);
+
+        let status = if self.status.is_empty() {
+            "r: refresh  a/Enter: switch  q: quit"
+        } else {
+            self.status.as_str()
+        };
+        f.render_widget(Paragraph::new(status), rows[1]);
+    }
+
+    fn run(&mut self, terminal: &mut Term) -> Result<()> {
+        loop {
+            if let Some(index) = self.pending() {
+                let status = std::mem::take(&mut self.status);
+                self.status = format!("Loading {}...", self.accounts[index].nickname);
+                terminal.draw(|f| self.draw(f))?;
+                self.accounts[index].load();
+                self.status = status;
+            }
+            terminal.draw(|f| self.draw(f))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && self.handle(key.code) {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn handle_dashboard(matches: &clap::ArgMatches) -> Result<()> {
+    let mut dashboard = Dashboard::new()?;
+    let output = Output::of(matches);
+    if output.is_table() {
+        return with_terminal(|terminal| dashboard.run(terminal));
+    }
+
+    // 非交互的输出需要所有账号的概况
+    dashboard.accounts.iter_mut().for_each(Account::load);
+    let accounts: Vec<Value> = dashboard
+        .accounts
+        .iter()
+        .map(|e| {
+            let (summary, error) = match &e.summary {
+                Some(Ok(summary)) => (Some(summary), None),
+                Some(Err(err)) => (None, Some(err)),
+                None => (None, None),
+            };
+            json!({
+                "nickname": e.nickname,
+                "active": dashboard.active.as_ref() == Some(&e.nickname),
+                "expires": e.expires,
+                "summary": summary,
+                "error": error,
+            })
+        })
+        .collect();
+    output.print(accounts.as_slice(), |_| ())
+}
@@ -0,0 +1,60 @@
+use super::{book_store, login_as, new_proxy, profile, Output};
+use crate::export::TextExporter;
+
+use anyhow::Result;
+use colored::*;
+use serde_json::json;
+use std::fs;
+
+pub fn handle_export(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = match matches.get_one::<String>("user") {
+        Some(user) => login_as(Some(user))?,
+        None => new_proxy()?,
+    };
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+    let annotations = *matches.get_one::<usize>("annotations").unwrap();
+
+    let library = book_store()?.library()?;
+    let exported = TextExporter::new(&proxy)
+        .with_library(&library)
+        .with_annotations(annotations)
+        .with_barrages(matches.get_flag("barrages"))
+        .with_concurrency(profile().concurrency())
+        .export(novel_id)?;
+    if !exported.locked_chapters.is_empty() {
+        eprintln!(
+            "{}: {} locked chapters exported without content",
+            "Warning".bold().yellow(),
+            exported.locked_chapters.len()
+        );
+    }
+    if !exported.unaligned_chapters.is_empty() {
+        eprintln!(
+            "{}: annotations of {} chapters are skipped as their paragraphs do not match the server's rows",
+            "Warning".bold().yellow(),
+            exported.unaligned_chapters.len()
+        );
+    }
+
+    let output = Output::of(matches);
+    let report = json!({
+        "novel_id": novel_id,
+        "locked_chapters": exported.locked_chapters,
+        "unaligned_chapters": exported.unaligned_chapters,
+    });
+    match matches.get_one::<String>("out") {
+        Some(path) => {
+            fs::write(path, &exported.text)?;
+            let mut report = report;
+            report["path"] = json!(path);
+            output.print(&report, |_| {
+                println!("Exported {} to {}", novel_id.to_string().bold(), path)
+            })
+        }
+        None => {
+            let mut report = report;
+            report["text"] = json!(exported.text);
+            output.print(&report, |_| print!("{}", exported.text))
+        }
+    }
+}
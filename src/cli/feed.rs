@@ -0,0 +1,62 @@
+use super::{login_as, new_proxy, Output};
+use crate::{
+    feed::{FeedFormat, FeedGenerator},
+    Id,
+};
+
+use anyhow::{bail, Result};
+use colored::*;
+use serde_json::json;
+use std::fs;
+
+pub fn handle_feed(matches: &clap::ArgMatches) -> Result<()> {
+    let user = matches.get_one::<String>("user");
+    let proxy = match user {
+        Some(user) => login_as(Some(user))?,
+        None => login_as(None).or_else(|_| new_proxy())?,
+    };
+
+    let mut novels: Vec<Id> = matches
+        .get_many::<i32>("NOVEL")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    if novels.is_empty() {
+        if !proxy.is_authenticated() {
+            bail!("no novel specified and no authenticated user to read the bookshelf of");
+        }
+        for pocket in proxy.favoirtes()? {
+            let refs = pocket.expand.and_then(|e| e.novels).unwrap_or_default();
+            novels.extend(refs.into_iter().map(|e| e.novelId));
+        }
+    }
+
+    let format = match matches.get_one::<String>("format").unwrap().as_str() {
+        "rss" => FeedFormat::Rss,
+        _ => FeedFormat::Atom,
+    };
+    let mut generator = FeedGenerator::new(&proxy)
+        .with_limit(*matches.get_one::<usize>("limit").unwrap())
+        .with_excerpt(*matches.get_one::<usize>("excerpt").unwrap());
+    if let Some(title) = matches.get_one::<String>("title") {
+        generator = generator.with_title(title);
+    }
+    let xml = generator.generate(format, &novels)?;
+
+    let output = Output::of(matches);
+    match matches.get_one::<String>("out") {
+        Some(path) => {
+            fs::write(path, xml)?;
+            output.print(&json!({ "novels": novels, "path": path }), |_| {
+                println!(
+                    "Generated the feed of {} novels to {}",
+                    novels.len().to_string().bold(),
+                    path
+                )
+            })
+        }
+        None => output.print(&json!({ "novels": novels, "xml": xml }), |_| {
+            print!("{xml}")
+        }),
+    }
+}
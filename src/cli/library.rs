@@ -0,0 +1,140 @@
+use super::{book_store, confirm, format_date, login_as, profile, Output};
+use crate::{download::Downloader, local::LibraryQuery, Id};
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde_json::json;
+
+pub fn handle_library(matches: &clap::ArgMatches) -> Result<()> {
+    let store = book_store()?;
+
+    match matches.subcommand() {
+        Some(("add", matches)) => {
+            let proxy = login_as(matches.get_one::<String>("user"))?;
+            let downloader = Downloader::new(&proxy).with_concurrency(profile().concurrency());
+            let output = Output::of(matches);
+            let mut added = vec![];
+            for novel_id in matches.get_many::<Id>("NOVEL").unwrap() {
+                let count = downloader.download(&store, *novel_id)?;
+                if output.is_table() {
+                    println!(
+                        "Added {} with {count} new chapters",
+                        novel_id.to_string().bold()
+                    );
+                }
+                added.push(json!({ "novel_id": novel_id, "new_chapters": count }));
+            }
+            output.print(&json!({ "novels": added }), |_| ())?;
+        }
+        Some(("ls", matches)) => {
+            let finished = match (matches.get_flag("finished"), matches.get_flag("ongoing")) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            let query = LibraryQuery {
+                author: matches.get_one::<String>("author").cloned(),
+                tag: matches.get_one::<String>("tag").cloned(),
+                finished,
+                min_chars: matches.get_one::<usize>("min-chars").copied(),
+                max_chars: matches.get_one::<usize>("max-chars").copied(),
+            };
+            let entries = store.library()?.query(&query)?;
+            Output::of(matches).print(entries.as_slice(), |entries| {
+                for entry in entries {
+                    println!(
+                        "{} ({})  {}  {} chars  {}/{} chapters  {}{}",
+                        entry.name.bold(),
+                        entry.novel_id,
+                        entry.author,
+                        entry.total_chars,
+                        entry.stored_chapters,
+                        entry.total_chapters,
+                        format_date(entry.last_update_time),
+                        if entry.is_finished { "  finished" } else { "" },
+                    );
+                }
+            })?;
+        }
+        Some(("show", matches)) => {
+            let novel_id = *matches.get_one::<Id>("NOVEL").unwrap();
+            let library = store.library()?;
+            let Some(novel) = library.novel(novel_id)? else {
+                return Err(anyhow!("novel {novel_id} is not in the library"));
+            };
+            let volumes = library.volumes_of(novel_id)?;
+            let value = json!({ "novel": novel, "volumes": volumes });
+            Output::of(matches).print(&value, |_| {
+                println!("{} ({})", novel.name.bold(), novel.id);
+                println!("{}  {}", novel.author, novel.tags.join(" "));
+                for volume in &volumes {
+                    println!("{}", volume.title.bold());
+                    for chapter in &volume.chapters {
+                        let status = match (chapter.fetch_time, chapter.is_free) {
+                            (Some(_), _) => "stored".green(),
+                            (None, true) => "free".normal(),
+                            (None, false) if chapter.is_purchased => "purchased".yellow(),
+                            (None, false) => "locked".red(),
+                        };
+                        println!("  {} ({})  {status}", chapter.title, chapter.id);
+                    }
+                }
+            })?;
+        }
+        Some(("rm", matches)) => {
+            let novels: Vec<Id> = matches.get_many::<Id>("NOVEL").unwrap().copied().collect();
+            let ids: Vec<String> = novels.iter().map(Id::to_string).collect();
+            if !matches.get_flag("yes")
+                && !confirm(&format!("Remove {} from the library?", ids.join(", ")))?
+            {
+                return Ok(());
+            }
+            let library = store.library()?;
+            let mut removed = vec![];
+            for novel_id in novels {
+                if library.remove(novel_id)? {
+                    store.remove_cover(novel_id)?;
+                    removed.push(novel_id);
+                }
+            }
+            Output::of(matches).print(&json!({ "novels": removed }), |value| {
+                println!(
+                    "Removed {} novels",
+                    value["novels"].as_array().unwrap().len()
+                );
+            })?;
+        }
+        Some(("search", matches)) => {
+            let query = matches.get_one::<String>("QUERY").unwrap();
+            let novel_id = matches.get_one::<Id>("novel").copied();
+            let limit = *matches.get_one::<usize>("limit").unwrap();
+            let hits = store.library()?.search(query, novel_id, limit)?;
+            Output::of(matches).print(hits.as_slice(), |hits| {
+                for hit in hits {
+                    println!(
+                        "{} ({})  {} / {} ({})",
+                        hit.novel_name.bold(),
+                        hit.novel_id,
+                        hit.volume,
+                        hit.chapter,
+                        hit.chapter_id,
+                    );
+                    println!("  {}", highlight(&hit.snippet, query));
+                }
+            })?;
+        }
+        Some(("vacuum", _)) => store.library()?.vacuum()?,
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+/// 加粗摘要中出现的查询
+fn highlight(snippet: &str, query: &str) -> String {
+    let query = query.trim();
+    if query.is_empty() {
+        return snippet.to_string();
+    }
+    snippet.replace(query, &query.red().bold().to_string())
+}
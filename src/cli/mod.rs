@@ -0,0 +1,882 @@
+mod album;
+mod author;
+mod buy;
+mod comments;
+mod config;
+mod daemon;
+mod dashboard;
+mod export;
+mod feed;
+mod library;
+mod novel;
+mod opds;
+mod output;
+mod progress;
+mod rank;
+mod read;
+mod search;
+mod serve;
+mod spend;
+mod term;
+mod vote;
+mod watch;
+
+pub use album::*;
+pub use author::*;
+pub use buy::*;
+pub use comments::*;
+pub use config::*;
+pub use daemon::*;
+pub use dashboard::*;
+pub use export::*;
+pub use feed::*;
+pub use library::*;
+pub use novel::*;
+pub use opds::*;
+pub use output::*;
+pub use progress::*;
+pub use rank::*;
+pub use read::*;
+pub use search::*;
+pub use serve::*;
+pub use spend::*;
+pub use vote::*;
+pub use watch::*;
+
+use crate::{
+    api,
+    local::{data_local_dir, Profile},
+    Proxy, Timestamp, UserPrivate, Value,
+};
+
+use anyhow::{bail, Result};
+use chrono::Datelike;
+use clap::{arg, value_parser, ArgAction, ArgGroup, Command};
+use colored::*;
+use serde_json::json;
+use std::{
+    fs,
+    io::{self, Read, Write},
+};
+
+pub fn cli() -> Command {
+    let auth = Command::new("auth")
+        .about("Authenticate sfutils with boluobao")
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("login")
+                .about("Authenticate with a boluobao host")
+                .arg(arg!(-U --username <USERNAME> "The user to authenticate"))
+                .arg(
+                    arg!(-u --account <ACCOUNT> "The account to authenticate with")
+                        .required_unless_present("username"),
+                )
+                .arg(
+                    arg!(-p --password <PASSWORD> "The password to authenticate with")
+                        .required_unless_present("username"),
+                )
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("logout")
+                .about("Log out of a boluobao host")
+                .arg(arg!(-a --all "Logout all authenticated users"))
+                .arg(arg!([USER]... "Users to logout"))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("status")
+                .about("View authentication status")
+                .subcommand(Command::new("list").about("List authenticated users"))
+                .subcommand(
+                    Command::new("view")
+                        .about("Display the information of an authenticated user")
+                        .arg(arg!(<USER> "The user to display"))
+                        .arg_required_else_help(true),
+                )
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("refresh")
+                .about("Refresh stored authentication credentials")
+                .arg(arg!([USER]... "Users to refresh, defaults to all authenticated users")),
+        );
+
+    let spend = Command::new("spend")
+        .about("Report fire coins spent by an authenticated user")
+        .arg(arg!(-U --user <USER> "The user to report, defaults to the active user"))
+        .arg(
+            arg!(--novel <NOVEL> "Only report purchases of the novel")
+                .value_parser(value_parser!(i32)),
+        )
+        .arg(arg!(--since <DATE> "Only report purchases made since the date"));
+
+    let buy = Command::new("buy")
+        .about("Buy locked chapters of a novel")
+        .arg(arg!(-U --user <USER> "The user to buy with, defaults to the active user"))
+        .arg(arg!(<NOVEL> "The novel to buy chapters of").value_parser(value_parser!(i32)))
+        .arg(
+            arg!(-c --chapter <CHAPTER> "The chapter to buy")
+                .value_parser(value_parser!(i32))
+                .action(ArgAction::Append),
+        )
+        .arg(arg!(-r --range <RANGE> "The inclusive chapter range to buy, e.g. 1001:1010"))
+        .arg(arg!(-a --all "Buy all the locked chapters"))
+        .group(
+            ArgGroup::new("chapters")
+                .args(["chapter", "range", "all"])
+                .required(true),
+        )
+        .arg(
+            arg!(-b --budget <BUDGET> "The maximum fire coins to spend")
+                .value_parser(value_parser!(usize))
+                .required(true),
+        )
+        .arg(arg!(--"dry-run" "Print the itemized cost without buying"))
+        .arg(arg!(-y --yes "Skip the confirmation prompt"))
+        .arg_required_else_help(true);
+
+    let comments = Command::new("comments")
+        .about("View comments and reviews of a novel")
+        .arg(arg!(<NOVEL> "The novel to view comments of").value_parser(value_parser!(i32)))
+        .arg(arg!(-l --long "View long reviews instead of comments"))
+        .arg(arg!(--sort <SORT> "Sort order, smart|timeline for comments and addtime for reviews"))
+        .arg(
+            arg!(--type <TYPE> "Comments to view, clear|stick|stickandclear")
+                .default_value("clear"),
+        )
+        .arg(
+            arg!(--charlen <CHARLEN> "Truncated length of reviews")
+                .value_parser(value_parser!(usize))
+                .default_value("140"),
+        )
+        .arg(
+            arg!(-n --limit <LIMIT> "The maximum number of comments to view")
+                .value_parser(value_parser!(usize))
+                .default_value("20"),
+        )
+        .arg(arg!(--json "Export comment threads as JSON, same as --output json"))
+        .arg_required_else_help(true);
+
+    let export = Command::new("export")
+        .about("Export the text of a novel")
+        .arg(
+            arg!(-U --user <USER> "The user to export with, locked chapters are exported as guest"),
+        )
+        .arg(arg!(<NOVEL> "The novel to export").value_parser(value_parser!(i32)))
+        .arg(arg!(-o --out <FILE> "The file to export to, defaults to stdout"))
+        .arg(
+            arg!(--annotations <N> "Inline the top N annotations of each paragraph as footnotes, one request per paragraph")
+                .value_parser(value_parser!(usize))
+                .default_value("0"),
+        )
+        .arg(arg!(--barrages "Append the barrages of each chapter after its text"))
+        .arg_required_else_help(true);
+
+    let rank = Command::new("rank")
+        .about("View ticket or bonus rankings of a novel")
+        .arg(arg!(<NOVEL> "The novel to view rankings of").value_parser(value_parser!(i32)))
+        .arg(
+            arg!(--by <BY> "The ranking to view")
+                .value_parser(["ticket", "bonus"])
+                .default_value("ticket"),
+        )
+        .arg(
+            arg!(--range <RANGE> "The date range of the ranking, the mapping to the API is unverified")
+                .value_parser(["day", "week", "month"])
+                .default_value("month"),
+        )
+        .arg(
+            arg!(-n --limit <LIMIT> "The maximum number of entries to view")
+                .value_parser(value_parser!(usize))
+                .default_value("50"),
+        )
+        .arg(arg!(--mine "View the rank of an authenticated user instead"))
+        .arg(arg!(-U --user <USER> "The user to view the rank of, defaults to the active user"))
+        .arg_required_else_help(true);
+
+    let vote = Command::new("vote")
+        .about("Vote monthly tickets or tip fire coins to a novel (experimental, unverified endpoints)")
+        .arg(arg!(-U --user <USER> "The user to act as, defaults to the active user"))
+        .arg(arg!(<NOVEL> "The novel to vote or tip").value_parser(value_parser!(i32)))
+        .arg(
+            arg!(-t --tickets <N> "The monthly tickets to vote").value_parser(value_parser!(usize)),
+        )
+        .arg(arg!(-b --bonus <N> "The fire coins to tip").value_parser(value_parser!(usize)))
+        .group(
+            ArgGroup::new("action")
+                .args(["tickets", "bonus"])
+                .required(true),
+        )
+        .arg(
+            arg!(-l --limit <LIMIT> "The maximum tickets or fire coins to spend")
+                .value_parser(value_parser!(usize))
+                .required(true),
+        )
+        .arg(arg!(--"dry-run" "Check the action without performing it"))
+        .arg(arg!(--experimental "Send through the unverified endpoints, required unless --dry-run"))
+        .arg(arg!(-y --yes "Skip the confirmation prompt"))
+        .arg_required_else_help(true);
+
+    let progress = Command::new("progress")
+        .about("Manage reading progress and sync it with boluobao")
+        .subcommand(Command::new("list").about("List local reading progress"))
+        .subcommand(
+            Command::new("history")
+                .about("List read history of an authenticated user")
+                .arg(arg!(-U --user <USER> "The user to list, defaults to the active user")),
+        )
+        .subcommand(
+            Command::new("set")
+                .about("Set local reading progress of a novel")
+                .arg(arg!(<NOVEL> "The novel read").value_parser(value_parser!(i32)))
+                .arg(arg!(<CHAPTER> "The chapter read").value_parser(value_parser!(i32)))
+                .arg(
+                    arg!(--row <ROW> "The paragraph row read")
+                        .value_parser(value_parser!(usize))
+                        .default_value("0"),
+                )
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("pull")
+                .about("Pull bookmarks into local progress, defaults to novels in read history")
+                .arg(arg!(-U --user <USER> "The user to sync, defaults to the active user"))
+                .arg(arg!([NOVEL]... "The novels to pull").value_parser(value_parser!(i32))),
+        )
+        .subcommand(
+            Command::new("push")
+                .about("Push local progress to bookmarks, defaults to all novels")
+                .arg(arg!(-U --user <USER> "The user to sync, defaults to the active user"))
+                .arg(arg!([NOVEL]... "The novels to push").value_parser(value_parser!(i32))),
+        )
+        .arg_required_else_help(true);
+
+    let read = Command::new("read")
+        .about("Read a novel in the terminal")
+        .arg(arg!(-U --user <USER> "The user to read as, defaults to the active user"))
+        .arg(arg!(<NOVEL> "The novel to read").value_parser(value_parser!(i32)))
+        .arg_required_else_help(true);
+
+    let dashboard =
+        Command::new("dashboard").about("Show an overview of all the authenticated users");
+
+    let search = Command::new("search")
+        .about("Search novels, comics and albums")
+        .arg(arg!(<KEYWORD> "The keyword to search"))
+        .arg(
+            arg!(--type <TYPE> "Only show works of the type")
+                .value_parser(["novel", "comic", "album"]),
+        )
+        .arg(arg!(--finished "Only show finished works"))
+        .arg(
+            arg!(--"min-chars" <CHARS> "Only show novels with at least the number of characters")
+                .value_parser(value_parser!(usize))
+                .default_value("0"),
+        )
+        .arg(
+            arg!(--sort <SORT> "Sort the results in descending order, only the first 200 results of each type are ranked")
+                .value_parser(["views", "marks", "update"]),
+        )
+        .arg(
+            arg!(-n --limit <LIMIT> "The maximum number of results to show")
+                .value_parser(value_parser!(usize))
+                .default_value("20"),
+        )
+        .arg(
+            arg!(-f --format <FORMAT> "The output format, same as --output")
+                .value_parser(["table", "json", "csv"]),
+        )
+        .arg_required_else_help(true);
+
+    let novel = Command::new("novel")
+        .about("View information of a novel")
+        .subcommand(
+            Command::new("info")
+                .about("Display the information of a novel")
+                .arg(arg!(<NOVEL> "The novel to display").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("toc")
+                .about("List volumes and chapters of a novel")
+                .arg(arg!(-U --user <USER> "The user to view prices for"))
+                .arg(arg!(<NOVEL> "The novel to list").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Compute statistics of a novel from its catalogue")
+                .arg(arg!(-U --user <USER> "The user to compute the cost of locked chapters for"))
+                .arg(arg!(<NOVEL> "The novel to compute").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .arg_required_else_help(true);
+
+    let config = Command::new("config")
+        .about("Manage profiles in the config file")
+        .subcommand(Command::new("list").about("List all the profiles"))
+        .subcommand(
+            Command::new("get")
+                .about("Get a config value of the profile")
+                .arg(arg!(<KEY> "The config key").value_parser(Profile::KEYS))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("set")
+                .about("Set a config value of the profile")
+                .arg(arg!(<KEY> "The config key").value_parser(Profile::KEYS))
+                .arg(arg!([VALUE] "The config value, removes the key if omitted"))
+                .arg_required_else_help(true),
+        )
+        .arg_required_else_help(true);
+
+    let daemon = Command::new("daemon")
+        .about("Run scheduled account tasks from the config file")
+        .subcommand(
+            Command::new("run")
+                .about("Run the scheduler in the foreground")
+                .arg(arg!(--once "Run the due tasks once without jitter and exit")),
+        )
+        .subcommand(Command::new("status").about("Show the last run of each scheduled task"))
+        .arg_required_else_help(true);
+
+    let watch = Command::new("watch")
+        .about("Watch the bookshelf and notify about new chapters")
+        .arg(arg!(-U --user <USER> "The user whose bookshelf to watch"))
+        .arg(
+            arg!(--interval <INTERVAL> "The interval between checks, such as 30m, 2h or 1d")
+                .default_value("30m"),
+        )
+        .arg(arg!(--once "Check the bookshelf once and exit"))
+        .arg(arg!(-q --quiet "Do not print notifications to stdout"))
+        .arg(arg!(--exec <COMMAND> "Run a shell command for each update").action(ArgAction::Append))
+        .arg(arg!(--webhook <URL> "POST each update as JSON to the URL").action(ArgAction::Append))
+        .arg(arg!(--log <FILE> "Append each update as a line of JSON to the file"));
+
+    let feed = Command::new("feed")
+        .about("Generate an Atom or RSS feed of the latest chapters")
+        .arg(
+            arg!([NOVEL] ... "The novels to include, defaults to the bookshelf of the user")
+                .value_parser(value_parser!(i32)),
+        )
+        .arg(arg!(-U --user <USER> "The user to fetch with, locked chapters have no excerpt"))
+        .arg(
+            arg!(--format <FORMAT> "The feed format")
+                .value_parser(["atom", "rss"])
+                .default_value("atom"),
+        )
+        .arg(arg!(--title <TITLE> "The title of the feed"))
+        .arg(
+            arg!(-n --limit <LIMIT> "The maximum number of chapters to include")
+                .value_parser(value_parser!(usize))
+                .default_value("50"),
+        )
+        .arg(
+            arg!(--excerpt <CHARS> "Include the first CHARS characters of each chapter")
+                .value_parser(value_parser!(usize))
+                .default_value("0"),
+        )
+        .arg(arg!(-o --out <FILE> "The file to write the feed to, defaults to stdout"));
+
+    let serve = Command::new("serve")
+        .about("Serve the API as local JSON endpoints")
+        .arg(arg!(--listen <ADDR> "The address to listen on").default_value("127.0.0.1:8080"))
+        .arg(arg!(-U --user <USER> "The default user of requests without a user"))
+        .arg(arg!(--token <TOKEN> "The access token of requests, generated if not given"))
+        .arg(
+            arg!(--"cache-ttl" <SECONDS> "The seconds to cache responses, 0 to disable")
+                .value_parser(value_parser!(u64))
+                .default_value("60"),
+        );
+
+    let opds = Command::new("opds")
+        .about("Serve the downloaded novels as an OPDS catalog")
+        .arg(
+            arg!(--listen <ADDR> "The address to listen on, the catalog has no access control")
+                .default_value("0.0.0.0:8081"),
+        )
+        .arg(arg!(--dir <DIR> "The download directory, defaults to the one of the profile"));
+
+    let library = Command::new("library")
+        .about("Manage the local library database of downloaded novels")
+        .subcommand(
+            Command::new("add")
+                .about("Download novels into the library or update them")
+                .arg(arg!(-U --user <USER> "The user to download as, defaults to the active user"))
+                .arg(arg!(<NOVEL>... "The novels to add").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("ls")
+                .about("List novels in the library")
+                .arg(arg!(--author <AUTHOR> "Only list novels whose author contains the text"))
+                .arg(arg!(--tag <TAG> "Only list novels with the tag"))
+                .arg(arg!(--finished "Only list finished novels").conflicts_with("ongoing"))
+                .arg(arg!(--ongoing "Only list ongoing novels"))
+                .arg(
+                    arg!(--"min-chars" <CHARS> "The minimum number of characters")
+                        .value_parser(value_parser!(usize)),
+                )
+                .arg(
+                    arg!(--"max-chars" <CHARS> "The maximum number of characters")
+                        .value_parser(value_parser!(usize)),
+                ),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("Show the catalogue of a novel and which chapters are stored")
+                .arg(arg!(<NOVEL> "The novel to show").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("rm")
+                .about("Remove novels and their chapters from the library")
+                .arg(arg!(<NOVEL>... "The novels to remove").value_parser(value_parser!(i32)))
+                .arg(arg!(-y --yes "Skip the confirmation prompt"))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("search")
+                .about("Search the stored chapter contents")
+                .arg(arg!(<QUERY> "The text to search for"))
+                .arg(
+                    arg!(--novel <NOVEL> "Only search chapters of the novel")
+                        .value_parser(value_parser!(i32)),
+                )
+                .arg(
+                    arg!(-n --limit <LIMIT> "The maximum number of results")
+                        .value_parser(value_parser!(usize))
+                        .default_value("20"),
+                )
+                .arg_required_else_help(true),
+        )
+        .subcommand(Command::new("vacuum").about("Reclaim unused space of the library database"))
+        .arg_required_else_help(true);
+
+    let album = Command::new("album")
+        .about("View and download audio albums")
+        .subcommand(
+            Command::new("info")
+                .about("Show an album and its tracks")
+                .arg(arg!(<ALBUM> "The album to show").value_parser(value_parser!(i32)))
+                .arg_required_else_help(true),
+        )
+        .subcommand(
+            Command::new("download")
+                .about("Download the unlocked tracks of an album")
+                .arg(arg!(-U --user <USER> "The user to download as, defaults to the active one or guest"))
+                .arg(arg!(<ALBUM> "The album to download").value_parser(value_parser!(i32)))
+                .arg(arg!(-o --out <DIR> "The directory to save the album to, defaults to download_dir or the current one"))
+                .arg(arg!(--"no-tags" "Do not write ID3 tags into MP3 files"))
+                .arg_required_else_help(true),
+        )
+        .arg_required_else_help(true);
+
+    let author = Command::new("author")
+        .about("Show an author with published novels and stats")
+        .arg(
+            arg!([USER_ID] "The user id (accountId) of the author to show, not the authorId of a novel; defaults to the user itself with announcements")
+                .value_parser(value_parser!(i32)),
+        )
+        .arg(arg!(-U --user <USER> "The user to view as, defaults to the active user"));
+
+    let query = Command::new("query")
+        .arg_required_else_help(true)
+        .arg(arg!(<URL>))
+        .arg(arg!([EXPAND]))
+        .arg(arg!(--params <PARAMS>));
+
+    Command::new("sfutils")
+        .about("An efficent yet powerful cli for boluobao")
+        .version("0.1.0")
+        .author("Zymelaii Ryer")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .arg(
+            arg!(--output <FORMAT> "The output format, defaults to table")
+                .global(true)
+                .value_parser(["table", "json", "yaml", "csv", "debug"]),
+        )
+        .arg(
+            arg!(--profile <PROFILE> "The config profile to use, defaults to default").global(true),
+        )
+        .subcommand(auth)
+        .subcommand(spend)
+        .subcommand(buy)
+        .subcommand(comments)
+        .subcommand(export)
+        .subcommand(rank)
+        .subcommand(vote)
+        .subcommand(progress)
+        .subcommand(read)
+        .subcommand(dashboard)
+        .subcommand(search)
+        .subcommand(novel)
+        .subcommand(config)
+        .subcommand(daemon)
+        .subcommand(watch)
+        .subcommand(feed)
+        .subcommand(serve)
+        .subcommand(opds)
+        .subcommand(library)
+        .subcommand(album)
+        .subcommand(author)
+        .subcommand(query)
+}
+
+impl Proxy {
+    pub fn get_local_credentials() -> Result<api::auth::AuthStatus> {
+        let data_file = data_local_dir()?.join("auth.toml");
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .open(data_file)?;
+
+        let mut buffer = String::default();
+        file.read_to_string(&mut buffer)?;
+
+        Ok(toml::from_str(&buffer)?)
+    }
+}
+
+fn local_storage() -> Result<toml::Table> {
+    let data_file = data_local_dir()?.join("auth.toml");
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .open(data_file)?;
+
+    let mut buffer = String::default();
+    file.read_to_string(&mut buffer)?;
+
+    Ok(buffer.parse::<toml::Table>().unwrap())
+}
+
+fn get_authenticated_users() -> Result<Vec<String>> {
+    Ok(local_storage()?.keys().into_iter().cloned().collect())
+}
+
+fn cleanup_auth() {
+    if let Ok(dir) = data_local_dir() {
+        let _ = fs::remove_file(dir.join("auth.toml"));
+    }
+}
+
+fn update_auth(proxy: &Proxy, profile: &api::types::UserPrivate, password: &str) -> Result<String> {
+    let mut data = local_storage()?;
+    let mut auth = toml::Table::new();
+
+    auth.insert(
+        "email".to_string(),
+        toml::Value::from(profile.email.to_owned()),
+    );
+
+    auth.insert(
+        "phone".to_string(),
+        toml::Value::from(profile.phoneNum.to_owned()),
+    );
+
+    auth.insert(
+        "password".to_string(),
+        toml::Value::from(password.to_owned()),
+    );
+
+    if let Some(credential) = proxy.credential() {
+        auth.insert("token".to_string(), toml::Value::from(credential.token));
+        auth.insert("session".to_string(), toml::Value::from(credential.session));
+        auth.insert(
+            "expires".to_string(),
+            toml::Value::from(credential.expires as i64),
+        );
+    }
+
+    data.insert(profile.nickName.to_owned(), toml::Value::from(auth));
+
+    let data_file = data_local_dir()?.join("auth.toml");
+    fs::write(data_file, data.to_string())?;
+
+    Ok(profile.nickName.to_owned())
+}
+
+fn remove_auth(users: &Vec<String>) -> Result<()> {
+    let mut data = local_storage()?;
+
+    users.iter().for_each(|user| {
+        data.remove(user);
+    });
+
+    let data_file = data_local_dir()?.join("auth.toml");
+    fs::write(data_file, data.to_string())?;
+
+    Ok(())
+}
+
+/// 依次使用指定的账号、配置档的默认账号、当前账号和首个已登录的账号
+fn resolve_user(user: Option<&String>) -> Result<String> {
+    Ok(match user.or(profile().account.as_ref()) {
+        Some(user) => user.to_owned(),
+        None => match get_active_user().or(get_authenticated_users()?.into_iter().next()) {
+            Some(user) => user,
+            None => bail!("no authenticated user"),
+        },
+    })
+}
+
+fn login_as(user: Option<&String>) -> Result<Proxy> {
+    let user = resolve_user(user)?;
+    let (account, password) = get_secrets_of(&user)?;
+    let mut proxy = new_proxy()?;
+    if let Some(msg) = proxy.login(&account, &password)? {
+        bail!(msg);
+    }
+    Ok(proxy)
+}
+
+/// 获取当前活跃的用户，未设置或已登出时返回 `None`
+fn get_active_user() -> Option<String> {
+    let file = data_local_dir().ok()?.join("active");
+    let user = fs::read_to_string(file).ok()?.trim().to_string();
+    get_authenticated_users()
+        .ok()?
+        .contains(&user)
+        .then_some(user)
+}
+
+fn set_active_user(user: &str) -> Result<()> {
+    fs::write(data_local_dir()?.join("active"), user)?;
+    Ok(())
+}
+
+/// 获取用户本地保存的凭证过期时间
+fn get_expires_of(username: &str) -> Option<Timestamp> {
+    local_storage()
+        .ok()?
+        .get(username)?
+        .get("expires")?
+        .as_integer()
+}
+
+/// 重新登录用户并保存新的凭证，返回凭证的过期时间
+fn refresh_auth(username: &str) -> Result<Timestamp> {
+    let (account, password) = get_secrets_of(username)?;
+    let mut proxy = new_proxy()?;
+    if let Some(msg) = proxy.login(&account, &password)? {
+        bail!(msg);
+    }
+    update_auth(&proxy, &proxy.profile()?, &password)?;
+    match proxy.credential() {
+        Some(credential) => Ok(credential.expires as Timestamp),
+        None => bail!("missing credential"),
+    }
+}
+
+/// 签到记录中是否包含今日
+fn is_signed_today(signs: &[api::types::SignInfo]) -> bool {
+    let today = chrono::Local::now().date_naive();
+    signs.iter().any(|e| {
+        (e.year, e.month as u32, e.day as u32) == (today.year(), today.month(), today.day())
+    })
+}
+
+fn format_date(timestamp: Timestamp) -> String {
+    chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0)
+        .map(|e| e.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// 按北京时间格式化 UNIX 时间戳，与服务端给出的日期一致
+fn format_unix_date(timestamp: Timestamp) -> String {
+    format_date(timestamp + crate::SERVER_UTC_OFFSET)
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{prompt} [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn get_secrets_of(username: &str) -> Result<(String, String)> {
+    match local_storage()?.get(username) {
+        Some(value) => {
+            let account = value.get("email").unwrap().as_str().unwrap().to_string();
+            let account = if account.is_empty() {
+                value.get("phone").unwrap().as_str().unwrap().to_string()
+            } else {
+                account
+            };
+            let password = value.get("password").unwrap().as_str().unwrap().to_string();
+            Ok((account, password))
+        }
+        None => bail!("unknown user"),
+    }
+}
+
+pub fn handle_auth_login(matches: &clap::ArgMatches) -> Result<()> {
+    let account: String;
+    let password: String;
+    if let Some(username) = matches.get_one::<String>("username") {
+        match get_secrets_of(&username) {
+            Ok(secrets) => {
+                (account, password) = secrets;
+            }
+            Err(_) => {
+                let hint = "Unknown user to be authenticated";
+                eprintln!("{}: {username}", hint.bold().red());
+                bail!(hint.to_lowercase());
+            }
+        }
+    } else {
+        account = matches.get_one::<String>("account").unwrap().to_owned();
+        password = matches.get_one::<String>("password").unwrap().to_owned();
+    }
+
+    let mut proxy = new_proxy()?;
+    if let Some(msg) = proxy.login(&account, &password)? {
+        let hint = "Authentication failed";
+        eprintln!("{}: {msg}", hint.bold().red());
+        bail!(hint);
+    }
+
+    let user = update_auth(&proxy, &proxy.profile()?, &password)?;
+    Output::of(matches).print(&json!({ "nickname": user }), |_| {
+        println!("Logged in to boluobao as {}", user.bold())
+    })
+}
+
+pub fn handle_auth_logout(matches: &clap::ArgMatches) -> Result<()> {
+    let users: Vec<String> = if matches.get_flag("all") {
+        let users = get_authenticated_users()?;
+        cleanup_auth();
+        users
+    } else {
+        let users: Vec<String> = matches.get_many("USER").unwrap().cloned().collect();
+        let _ = remove_auth(&users);
+        users
+    };
+
+    Output::of(matches).print(&json!({ "removed": users }), |_| ())
+}
+
+pub fn handle_auth_status(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("list", matches)) => {
+            let active = get_active_user();
+            let users: Vec<Value> = get_authenticated_users()?
+                .into_iter()
+                .map(|e| json!({ "active": active.as_ref() == Some(&e), "nickname": e }))
+                .collect();
+            Output::of(matches).print(&users, |users| {
+                for user in users {
+                    let nickname = user["nickname"].as_str().unwrap();
+                    if user["active"].as_bool().unwrap() {
+                        println!("{} (active)", nickname.bold())
+                    } else {
+                        println!("{}", nickname.bold())
+                    }
+                }
+            })?;
+        }
+        Some(("view", matches)) => {
+            let user = matches.get_one::<String>("USER").unwrap();
+            let users = get_authenticated_users().unwrap();
+            if users.contains(user) {
+                let (account, password) = get_secrets_of(&user).unwrap();
+                let mut proxy = new_proxy()?;
+                proxy.login(&account, &password)?;
+                let profile = proxy.profile()?.parse(proxy.money()?)?;
+                Output::of(matches).print(&profile, print_private)?;
+            } else {
+                let hint = "Unknown user";
+                eprintln!("{}: {}", hint.bold().red(), user);
+                bail!(hint);
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+fn print_private(profile: &UserPrivate) {
+    let vip = &profile.vip;
+    println!("{} ({})", profile.nickname.bold(), profile.id);
+    println!("  Registered: {}", format_date(profile.registration_time));
+    println!("  Phone: +{} {}", profile.area_code, profile.phone);
+    println!("  Email: {}", profile.email);
+    println!("  Author: {}", profile.is_author);
+    println!(
+        "  VIP: level {} ({}/{} points to level {}), {}% off",
+        vip.level, vip.point, vip.point_of_next_level, vip.next_level, vip.discount
+    );
+    println!(
+        "  Balance: {} fire coins",
+        profile.banlance.to_string().yellow()
+    );
+    println!("  Vouchers: {}", profile.vouchers);
+    println!(
+        "  Welfare: {} coins, {:.2} money",
+        profile.tokens, profile.welfare_money
+    );
+    let Some(nickname) = &profile.nickname_change else {
+        return;
+    };
+    match nickname.cost(vip.level) {
+        Some(cost) => println!("  Nickname: changeable, costs {cost} fire coins"),
+        None if nickname.can_change => println!("  Nickname: changeable with a rename card"),
+        None => println!(
+            "  Nickname: changeable in {} days",
+            nickname.days_until_next
+        ),
+    }
+}
+
+pub fn handle_auth_refresh(matches: &clap::ArgMatches) -> Result<()> {
+    let users: Vec<String> = match matches.get_many::<String>("USER") {
+        Some(users) => users.cloned().collect(),
+        None => get_authenticated_users()?,
+    };
+
+    let output = Output::of(matches);
+    let mut results = vec![];
+    for user in &users {
+        match refresh_auth(user) {
+            Ok(expires) => {
+                if output.is_table() {
+                    println!(
+                        "Refreshed {}, expires at {}",
+                        user.bold(),
+                        format_date(expires)
+                    );
+                }
+                results.push(json!({ "nickname": user, "expires": expires, "error": null }));
+            }
+            Err(err) => {
+                eprintln!("{}: {user}: {err}", "Refresh failed".bold().red());
+                results
+                    .push(json!({ "nickname": user, "expires": null, "error": err.to_string() }));
+            }
+        }
+    }
+    output.print(&results, |_| ())?;
+    if results.iter().any(|e| !e["error"].is_null()) {
+        bail!("refresh failed");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `arg!` 要求短选项写在长选项之前（如 `-U --user`），否则在调试构建中会直接 panic
+    #[test]
+    fn cli_is_well_formed() {
+        cli().debug_assert();
+    }
+}
@@ -0,0 +1,159 @@
+use super::{format_date, login_as, new_proxy, Output};
+use crate::{DerivativeWorks, Novel, NovelStats};
+
+use anyhow::Result;
+use chrono::Utc;
+use colored::*;
+use serde::Serialize;
+
+/// 小说信息与衍生作品，衍生作品获取失败时为 `None`
+#[derive(Debug, Serialize)]
+struct NovelInfo {
+    #[serde(flatten)]
+    novel: Novel,
+    derivative_works: Option<DerivativeWorks>,
+}
+
+pub fn handle_novel(matches: &clap::ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("info", matches)) => {
+            let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+            let proxy = new_proxy()?;
+            let novel = proxy.novel_info(novel_id)?.parse()?;
+            // 衍生作品获取失败不影响小说信息
+            let derivative_works = match proxy.derivative_works_of(novel_id) {
+                Ok(works) => Some(works.parse(novel_id)?),
+                Err(err) => {
+                    eprintln!(
+                        "{}: failed to get derivative works: {err}",
+                        "Warning".bold().yellow()
+                    );
+                    None
+                }
+            };
+            let info = NovelInfo {
+                novel,
+                derivative_works,
+            };
+            Output::of(matches).print(&info, |info| {
+                let novel = &info.novel;
+                println!("{} ({})", novel.name.bold(), novel.id);
+                println!("  Author: {} ({})", novel.author, novel.author_id);
+                println!("  Type: {}", novel.r#type);
+                println!("  Tags: {}", novel.tags.join(", "));
+                println!("  Sign: {} ({})", novel.sign_status, novel.sign_level);
+                println!(
+                    "  Status: {}, updated at {}",
+                    if novel.is_finished {
+                        "finished"
+                    } else {
+                        "serializing"
+                    },
+                    format_date(novel.last_update_time)
+                );
+                println!(
+                    "  Chapters: {}, {} characters",
+                    novel.total_chapters, novel.total_chars
+                );
+                println!(
+                    "  Views: {}, likes: {}, favorites: {}, tickets: {}",
+                    novel.total_views,
+                    novel.total_likes,
+                    novel.total_favorites,
+                    novel.total_tickets
+                );
+                println!("  Cover: {}", novel.cover);
+                println!("  Created: {}", format_date(novel.creation_time));
+                println!();
+                println!("{}", novel.intro);
+                if let Some(works) = &info.derivative_works {
+                    if !works.comics.is_empty() || !works.albums.is_empty() {
+                        println!();
+                        println!("{}", "Derivative works".bold());
+                    }
+                    for comic in &works.comics {
+                        println!("  Comic: {} ({})", comic.name, comic.id);
+                    }
+                    for album in &works.albums {
+                        println!("  Album: {} ({})", album.name, album.id);
+                    }
+                }
+            })?;
+        }
+        Some(("toc", matches)) => {
+            let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+            let proxy = match matches.get_one::<String>("user") {
+                Some(user) => login_as(Some(user))?,
+                None => new_proxy()?,
+            };
+            let volumes = proxy.catalogue_of(novel_id)?.parse()?;
+            Output::of(matches).print(volumes.as_slice(), |volumes| {
+                for volume in volumes {
+                    println!("{}", volume.title.bold());
+                    for chapter in &volume.chapters {
+                        let marker = if chapter.is_free {
+                            String::new()
+                        } else if chapter.price > 0 {
+                            format!("  [VIP {}]", chapter.price).yellow().to_string()
+                        } else {
+                            "  [VIP]".to_string()
+                        };
+                        println!(
+                            "{:>6}  {:>10}  {}{marker}",
+                            chapter.order, chapter.id, chapter.title
+                        );
+                    }
+                }
+            })?;
+        }
+        Some(("stats", matches)) => {
+            let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+            let proxy = match matches.get_one::<String>("user") {
+                Some(user) => login_as(Some(user))?,
+                None => new_proxy()?,
+            };
+            let volumes = proxy.catalogue_of(novel_id)?.parse()?;
+            let stats = NovelStats::new(novel_id, &volumes, Utc::now().timestamp());
+            Output::of(matches).print(&stats, |stats| {
+                for volume in &stats.volumes {
+                    println!(
+                        "{}  {} chapters, {} characters",
+                        volume.title.bold(),
+                        volume.total_chapters,
+                        volume.total_chars
+                    );
+                }
+                println!(
+                    "{}: {} chapters, {} characters",
+                    "Total".bold(),
+                    stats.total_chapters(),
+                    stats.total_chars()
+                );
+                if let (Some(first), Some(last)) =
+                    (stats.first_chapter_time, stats.last_chapter_time)
+                {
+                    println!(
+                        "Published from {} to {}",
+                        format_date(first),
+                        format_date(last)
+                    );
+                }
+                if let Some(interval) = stats.average_interval {
+                    println!("Updated every {interval:.1} days on average");
+                }
+                println!(
+                    "{} chapters published in the last 30 days",
+                    stats.recent_chapters
+                );
+                println!(
+                    "{} locked chapters, {} fire coins to unlock",
+                    stats.locked_chapters,
+                    stats.locked_cost.to_string().yellow()
+                );
+            })?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use super::book_store;
+use crate::{
+    ebook::{build_epub, build_txt},
+    local::{BookStore, Library},
+    opds::OpdsCatalog,
+    Id,
+};
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use std::path::PathBuf;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// 响应体与内容类型
+type Content = (Vec<u8>, &'static str);
+
+/// 处理请求，路径不存在时返回 `None`
+fn handle(store: &BookStore, library: &Library, request: &Request) -> Result<Option<Content>> {
+    let path = request.url().split('?').next().unwrap_or_default();
+    if let Some(path) = path.strip_prefix("/opds") {
+        let feed = OpdsCatalog::new(store, library)?.feed(path)?;
+        return Ok(feed.map(|e| {
+            let kind = "application/atom+xml;profile=opds-catalog;charset=utf-8";
+            (e.into_bytes(), kind)
+        }));
+    }
+    if path == "/" {
+        return Ok(Some((
+            br#"<a href="/opds">OPDS</a>"#.to_vec(),
+            "text/html;charset=utf-8",
+        )));
+    }
+
+    let Some(file) = path.strip_prefix("/books/") else {
+        return Ok(None);
+    };
+    let (id, file) = match file.split_once('/') {
+        Some((id, "cover.jpg")) => (id, "cover.jpg"),
+        Some(_) => return Ok(None),
+        None => match file.rsplit_once('.') {
+            Some((id, ext)) => (id, ext),
+            None => return Ok(None),
+        },
+    };
+    let Ok(novel_id) = id.parse::<Id>() else {
+        return Ok(None);
+    };
+    let Some(book) = library.book(novel_id)? else {
+        return Ok(None);
+    };
+    let content = match file {
+        "cover.jpg" => store.cover_of(book.id).map(|e| (e, "image/jpeg")),
+        "epub" => {
+            let cover = store.cover_of(book.id);
+            let epub = build_epub(&book, library, cover.as_deref())?;
+            Some((epub, "application/epub+zip"))
+        }
+        "txt" => {
+            let text = build_txt(&book, library)?;
+            Some((text.into_bytes(), "text/plain;charset=utf-8"))
+        }
+        _ => None,
+    };
+    Ok(content)
+}
+
+fn respond(store: &BookStore, library: &Library, request: Request) -> Result<()> {
+    let (status, (body, kind)) = if *request.method() != Method::Get {
+        (405, (b"method not allowed".to_vec(), "text/plain"))
+    } else {
+        match handle(store, library, &request) {
+            Ok(Some(content)) => (200, content),
+            Ok(None) => (404, (b"not found".to_vec(), "text/plain")),
+            Err(err) => (500, (err.to_string().into_bytes(), "text/plain")),
+        }
+    };
+    eprintln!("{} {} {status}", request.method(), request.url());
+    let response = Response::from_data(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", kind).unwrap());
+    request.respond(response)?;
+    Ok(())
+}
+
+pub fn handle_opds(matches: &clap::ArgMatches) -> Result<()> {
+    let listen = matches.get_one::<String>("listen").unwrap();
+    let store = match matches.get_one::<String>("dir") {
+        Some(dir) => BookStore::open_at(PathBuf::from(dir))?,
+        None => book_store()?,
+    };
+    let library = store.library()?;
+
+    let server =
+        Server::http(listen).map_err(|err| anyhow!("failed to listen on {listen}: {err}"))?;
+    eprintln!(
+        "{} http://{listen}/opds",
+        "Serving the OPDS catalog on".bold()
+    );
+    // 目录没有访问控制，监听非回环地址时任何能访问该地址的人都可以下载书库中的小说
+    if !listen.starts_with("127.") && !listen.starts_with("localhost:") {
+        eprintln!(
+            "{}: the catalog has no access control, anyone who can reach {listen} can download the library",
+            "Warning".bold().yellow()
+        );
+    }
+    for request in server.incoming_requests() {
+        if let Err(err) = respond(&store, &library, request) {
+            eprintln!("{}: {err}", "Failed to respond".bold().red());
+        }
+    }
+
+    Ok(())
+}
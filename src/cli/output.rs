@@ -0,0 +1,142 @@
+use super::profile;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::{fmt::Debug, io};
+
+/// 命令的输出格式，由全局参数 `--output` 指定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    Table,
+    Json,
+    Yaml,
+    Csv,
+    Debug,
+}
+
+impl Output {
+    pub fn of(matches: &clap::ArgMatches) -> Self {
+        let output = matches
+            .get_one::<String>("output")
+            .or(profile().output.as_ref());
+        output.map_or(Output::Table, |e| Self::named(e))
+    }
+
+    /// 由格式名得到输出格式，未知的格式名视为表格
+    pub fn named(name: &str) -> Self {
+        match name {
+            "json" => Output::Json,
+            "yaml" => Output::Yaml,
+            "csv" => Output::Csv,
+            "debug" => Output::Debug,
+            _ => Output::Table,
+        }
+    }
+
+    #[inline]
+    pub fn is_table(self) -> bool {
+        self == Output::Table
+    }
+
+    /// 以指定格式输出 `value`，表格格式下调用 `table` 输出人类可读的文本
+    ///
+    /// CSV 格式下列表的每个元素占一行，单个值占一行，嵌套的对象以 `a.b` 形式展开为列，
+    /// 嵌套的列表以 JSON 字符串的形式写入单元格；表头为所有行的列的并集，缺少的列留空
+    pub fn print<T: Serialize + Debug + ?Sized>(
+        self,
+        value: &T,
+        table: impl FnOnce(&T),
+    ) -> Result<()> {
+        match self {
+            Output::Table => table(value),
+            Output::Json => println!("{}", serde_json::to_string_pretty(value)?),
+            Output::Yaml => print!("{}", serde_yaml::to_string(value)?),
+            Output::Csv => write_csv(serde_json::to_value(value)?, io::stdout())?,
+            Output::Debug => println!("{value:#?}"),
+        }
+        Ok(())
+    }
+}
+
+fn flatten(prefix: &str, value: Value, row: &mut Vec<(String, String)>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                flatten(&key(&name), value, row);
+            }
+        }
+        Value::Null => row.push((prefix.to_string(), String::new())),
+        Value::String(text) => row.push((prefix.to_string(), text)),
+        value => row.push((prefix.to_string(), value.to_string())),
+    }
+}
+
+/// 写入 CSV，表头按首次出现的顺序取所有行的列的并集，行中缺少的列写为空字符串
+fn write_csv(value: Value, writer: impl io::Write) -> Result<()> {
+    let records = match value {
+        Value::Array(records) => records,
+        value => vec![value],
+    };
+    let rows: Vec<Vec<(String, String)>> = records
+        .into_iter()
+        .map(|e| {
+            let mut row = vec![];
+            match e {
+                Value::Object(_) => flatten("", e, &mut row),
+                e => flatten("value", e, &mut row),
+            }
+            row
+        })
+        .collect();
+
+    let mut header: Vec<&str> = vec![];
+    for (key, _) in rows.iter().flatten() {
+        if !header.contains(&key.as_str()) {
+            header.push(key);
+        }
+    }
+
+    let mut writer = csv::Writer::from_writer(writer);
+    if !rows.is_empty() {
+        writer.write_record(&header)?;
+    }
+    for row in &rows {
+        writer.write_record(header.iter().map(|key| {
+            row.iter()
+                .find(|(k, _)| k == key)
+                .map_or("", |(_, v)| v.as_str())
+        }))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn csv_header_is_the_union_of_all_rows() {
+        let value = json!([
+            { "nickname": "a", "summary": null, "error": "failed" },
+            { "nickname": "b", "summary": { "shelf": 3, "tags": ["x"] }, "error": null },
+        ]);
+        let mut buffer = vec![];
+        write_csv(value, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "error,nickname,summary,summary.shelf,summary.tags\n\
+             failed,a,,,\n\
+             ,b,,3,\"[\"\"x\"\"]\"\n"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use super::{format_unix_date, login_as, Output};
+use crate::{local::ProgressStore, Id};
+
+use anyhow::Result;
+use colored::*;
+use serde_json::json;
+
+pub fn handle_progress(matches: &clap::ArgMatches) -> Result<()> {
+    let mut store = ProgressStore::open()?;
+
+    match matches.subcommand() {
+        Some(("list", matches)) => {
+            let progress: Vec<_> = store.list().collect();
+            Output::of(matches).print(progress.as_slice(), |progress| {
+                for progress in progress {
+                    println!(
+                        "{}  chapter {} row {}  {}",
+                        progress.novel_id.to_string().bold(),
+                        progress.chapter_id,
+                        progress.row,
+                        format_unix_date(progress.update_time),
+                    );
+                }
+            })?;
+        }
+        Some(("history", matches)) => {
+            let proxy = login_as(matches.get_one::<String>("user"))?;
+            let history = proxy
+                .read_history(0, 50)?
+                .into_iter()
+                .map(|e| e.parse())
+                .collect::<Result<Vec<_>>>()?;
+            Output::of(matches).print(history.as_slice(), |history| {
+                for view in history {
+                    println!(
+                        "{} ({})  {}  {}",
+                        view.novel_name.bold(),
+                        view.novel_id,
+                        view.chapter_title.as_deref().unwrap_or_default(),
+                        view.view_time,
+                    );
+                }
+            })?;
+        }
+        Some(("set", matches)) => {
+            let novel_id = *matches.get_one::<Id>("NOVEL").unwrap();
+            let chapter_id = *matches.get_one::<Id>("CHAPTER").unwrap();
+            let row = *matches.get_one::<usize>("row").unwrap();
+            store.mark(novel_id, chapter_id, row);
+            store.save()?;
+            Output::of(matches).print(&store.get(novel_id), |_| ())?;
+        }
+        Some(("pull", matches)) => {
+            let proxy = login_as(matches.get_one::<String>("user"))?;
+            let novels: Vec<Id> = match matches.get_many::<Id>("NOVEL") {
+                Some(novels) => novels.copied().collect(),
+                None => proxy
+                    .read_history(0, 50)?
+                    .into_iter()
+                    .map(|e| e.novelId)
+                    .collect(),
+            };
+            let output = Output::of(matches);
+            let (mut pulled, mut failed) = (vec![], vec![]);
+            for novel_id in novels {
+                match store.pull(&proxy, novel_id) {
+                    Ok(true) => {
+                        if output.is_table() {
+                            println!("Pulled progress of {}", novel_id.to_string().bold());
+                        }
+                        pulled.push(novel_id);
+                    }
+                    Ok(false) => (),
+                    Err(err) => {
+                        eprintln!("{} {novel_id}: {err}", "Failed to pull".red());
+                        failed.push(novel_id);
+                    }
+                }
+            }
+            store.save()?;
+            output.print(&json!({ "novels": pulled, "failed": failed }), |_| ())?;
+        }
+        Some(("push", matches)) => {
+            let proxy = login_as(matches.get_one::<String>("user"))?;
+            let novels: Vec<Id> = match matches.get_many::<Id>("NOVEL") {
+                Some(novels) => novels.copied().collect(),
+                None => store.list().map(|e| e.novel_id).collect(),
+            };
+            let output = Output::of(matches);
+            let (mut pushed, mut failed) = (vec![], vec![]);
+            for novel_id in novels {
+                match store.push(&proxy, novel_id) {
+                    Ok(true) => {
+                        if output.is_table() {
+                            println!("Pushed progress of {}", novel_id.to_string().bold());
+                        }
+                        pushed.push(novel_id);
+                    }
+                    Ok(false) => (),
+                    Err(err) => {
+                        eprintln!("{} {novel_id}: {err}", "Failed to push".red());
+                        failed.push(novel_id);
+                    }
+                }
+            }
+            output.print(&json!({ "novels": pushed, "failed": failed }), |_| ())?;
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use super::{login_as, new_proxy, Output};
+
+use anyhow::Result;
+use colored::*;
+
+pub fn handle_rank(matches: &clap::ArgMatches) -> Result<()> {
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+    let limit = *matches.get_one::<usize>("limit").unwrap();
+    let by_ticket = matches.get_one::<String>("by").unwrap() == "ticket";
+    let date_range = match matches.get_one::<String>("range").unwrap().as_str() {
+        "day" => 1,
+        "week" => 2,
+        "month" => 3,
+        _ => unreachable!(),
+    };
+    let unit = if by_ticket { "tickets" } else { "fire coins" };
+
+    if matches.get_flag("mine") {
+        let proxy = login_as(matches.get_one::<String>("user"))?;
+        let ranking = if by_ticket {
+            proxy.user_ticket_rank_of(novel_id, limit, date_range)?
+        } else {
+            proxy.user_bonus_rank_of(novel_id, limit, date_range)?
+        };
+        return Output::of(matches).print(&ranking.parse()?, |ranking| match ranking.rank {
+            Some(rank) => println!(
+                "Ranked {} with {} {unit}",
+                rank.to_string().bold(),
+                ranking.amount
+            ),
+            None => println!("Not ranked with {} {unit}", ranking.amount),
+        });
+    }
+
+    let proxy = new_proxy()?;
+    let entries = if by_ticket {
+        proxy.ticket_rank_of(novel_id, limit, date_range)?
+    } else {
+        proxy.bonus_rank_of(novel_id, limit, date_range)?
+    };
+    let entries = entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, e)| e.parse(index + 1))
+        .collect::<Result<Vec<_>>>()?;
+    Output::of(matches).print(entries.as_slice(), |entries| {
+        for entry in entries {
+            println!(
+                "{:>4}  {} ({})  {} {unit}",
+                entry.rank,
+                entry.nickname.bold(),
+                entry.user_id,
+                entry.amount.to_string().yellow(),
+            );
+        }
+    })
+}
@@ -0,0 +1,482 @@
+use super::{
+    book_store, login_as, new_proxy,
+    term::{char_width, with_terminal, Term},
+    Output,
+};
+use crate::{
+    local::{ChapterCache, Library, ProgressStore},
+    Chapter, Id, Proxy, Volume,
+};
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+#[derive(PartialEq, Eq)]
+enum Focus {
+    Catalogue,
+    Text,
+}
+
+struct Line {
+    text: String,
+    row: usize, //<! 所在段落的行号
+}
+
+struct Reader<'a> {
+    proxy: &'a Proxy,
+    novel_id: Id,
+    novel_name: String,
+    volumes: Vec<Volume>,
+    entries: Vec<(usize, Option<usize>)>, //<! 目录项，(卷序号, 章节序号)
+    chapters: Vec<(usize, usize)>,        //<! 按阅读顺序排列的章节
+    catalogue: ListState,
+    current: Option<usize>, //<! 当前章节在 chapters 中的序号
+    paragraphs: Vec<String>,
+    lines: Vec<Line>,
+    width: usize,
+    height: usize,
+    offset: usize,
+    focus: Focus,
+    search: Option<String>,
+    query: String,
+    matches: Vec<usize>,
+    status: String,
+    library: Library,    //<! 下载目录中的书库
+    cache: ChapterCache, //<! 书库中没有的小说的章节缓存
+    progress: ProgressStore,
+}
+
+impl<'a> Reader<'a> {
+    fn new(proxy: &'a Proxy, novel_id: Id) -> Result<Self> {
+        let store = book_store()?;
+        let novel_name = proxy.novel_info(novel_id)?.novelName;
+        let volumes = proxy.catalogue_of(novel_id)?.parse()?;
+        let mut entries = vec![];
+        let mut chapters = vec![];
+        for (i, volume) in volumes.iter().enumerate() {
+            entries.push((i, None));
+            for j in 0..volume.chapters.len() {
+                entries.push((i, Some(j)));
+                chapters.push((i, j));
+            }
+        }
+        let mut catalogue = ListState::default();
+        catalogue.select(Some(0));
+        Ok(Self {
+            proxy,
+            novel_id,
+            novel_name,
+            volumes,
+            entries,
+            chapters,
+            catalogue,
+            current: None,
+            paragraphs: vec![],
+            lines: vec![],
+            width: 0,
+            height: 0,
+            offset: 0,
+            focus: Focus::Catalogue,
+            search: None,
+            query: String::new(),
+            matches: vec![],
+            status: String::from("Tab: switch pane  /: search  b: bookmark  q: quit"),
+            library: store.library()?,
+            cache: store.cache_of(novel_id)?,
+            progress: ProgressStore::open()?,
+        })
+    }
+
+    fn chapter_at(&self, index: usize) -> &Chapter {
+        let (i, j) = self.chapters[index];
+        &self.volumes[i].chapters[j]
+    }
+
+    /// 获取章节段落，依次使用书库、本地缓存与服务端，未解锁的章节显示价格
+    fn load(&self, chapter: &Chapter) -> Vec<String> {
+        let stored = self.library.content_of(chapter.id).ok().flatten();
+        if let Some(content) = stored.or_else(|| self.cache.get(chapter.id)) {
+            return split_paragraphs(&content);
+        }
+        let locked = format!(
+            "[VIP] {} fire coins (origin {}) to unlock this chapter",
+            chapter.price, chapter.origin_price
+        );
+        if !chapter.is_free && !self.proxy.is_authenticated() {
+            return vec![locked];
+        }
+        match self.proxy.chapter_content(chapter.id) {
+            Ok(resp) => match resp.expand.and_then(|e| e.content) {
+                Some(content) if !content.is_empty() => {
+                    // 书库中的小说保存到书库，其它小说保存到本地缓存
+                    let now = Utc::now().timestamp();
+                    if !matches!(
+                        self.library.put_content(chapter.id, &content, now),
+                        Ok(true)
+                    ) {
+                        let _ = self.cache.put(chapter.id, &content);
+                    }
+                    split_paragraphs(&content)
+                }
+                _ => vec![locked],
+            },
+            Err(_) if !chapter.is_free => vec![locked],
+            Err(err) => vec![format!("Failed to load chapter: {err}")],
+        }
+    }
+
+    fn open(&mut self, index: usize, row: usize) {
+        let chapter = self.chapter_at(index);
+        let (chapter_id, title) = (chapter.id, chapter.title.to_owned());
+        self.paragraphs = self.load(self.chapter_at(index));
+        self.current = Some(index);
+        self.offset = 0;
+        self.matches.clear();
+        self.wrap();
+        self.offset = self
+            .lines
+            .iter()
+            .position(|e| e.row >= row)
+            .unwrap_or_default();
+        let entry = self
+            .entries
+            .iter()
+            .position(|e| *e == (self.chapters[index].0, Some(self.chapters[index].1)));
+        self.catalogue.select(entry);
+        self.progress.mark(self.novel_id, chapter_id, row);
+        self.status = title;
+    }
+
+    fn open_chapter(&mut self, chapter_id: Id, row: usize) {
+        if let Some(index) = (0..self.chapters.len()).find(|e| self.chapter_at(*e).id == chapter_id)
+        {
+            self.open(index, row);
+        }
+    }
+
+    /// 按当前宽度折行
+    fn wrap(&mut self) {
+        let row = self.lines.get(self.offset).map_or(0, |e| e.row);
+        self.lines.clear();
+        let width = self.width.max(2);
+        for (index, paragraph) in self.paragraphs.iter().enumerate() {
+            let mut text = String::new();
+            let mut used = 0;
+            for c in paragraph.chars() {
+                if used + char_width(c) > width {
+                    self.lines.push(Line {
+                        text: std::mem::take(&mut text),
+                        row: index + 1,
+                    });
+                    used = 0;
+                }
+                used += char_width(c);
+                text.push(c);
+            }
+            self.lines.push(Line {
+                text,
+                row: index + 1,
+            });
+        }
+        self.offset = self
+            .lines
+            .iter()
+            .position(|e| e.row >= row)
+            .unwrap_or_default();
+    }
+
+    fn row(&self) -> usize {
+        self.lines.get(self.offset).map_or(0, |e| e.row)
+    }
+
+    fn mark(&mut self) {
+        if let Some(index) = self.current {
+            let chapter_id = self.chapter_at(index).id;
+            self.progress.mark(self.novel_id, chapter_id, self.row());
+        }
+    }
+
+    fn next_page(&mut self) {
+        if self.offset + self.height < self.lines.len() {
+            self.offset += self.height;
+        } else {
+            self.next_chapter();
+        }
+    }
+
+    fn prev_page(&mut self) {
+        if self.offset > 0 {
+            self.offset = self.offset.saturating_sub(self.height);
+        } else {
+            self.prev_chapter();
+        }
+    }
+
+    fn next_chapter(&mut self) {
+        match self.current {
+            Some(index) if index + 1 < self.chapters.len() => self.open(index + 1, 0),
+            None if !self.chapters.is_empty() => self.open(0, 0),
+            _ => self.status = String::from("No more chapters"),
+        }
+    }
+
+    fn prev_chapter(&mut self) {
+        match self.current {
+            Some(index) if index > 0 => self.open(index - 1, 0),
+            _ => self.status = String::from("No previous chapters"),
+        }
+    }
+
+    fn find(&mut self) {
+        self.matches = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !self.query.is_empty() && e.text.contains(&self.query))
+            .map(|(i, _)| i)
+            .collect();
+        self.status = format!("{} matches of \"{}\"", self.matches.len(), self.query);
+        self.next_match();
+    }
+
+    fn next_match(&mut self) {
+        if let Some(line) = self.matches.iter().find(|e| **e > self.offset) {
+            self.offset = *line;
+        } else if let Some(line) = self.matches.first() {
+            self.offset = *line;
+        }
+    }
+
+    fn prev_match(&mut self) {
+        if let Some(line) = self.matches.iter().rev().find(|e| **e < self.offset) {
+            self.offset = *line;
+        } else if let Some(line) = self.matches.last() {
+            self.offset = *line;
+        }
+    }
+
+    fn select(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let selected = self.catalogue.selected().unwrap_or_default() as isize + delta;
+        let selected = selected.clamp(0, self.entries.len() as isize - 1);
+        self.catalogue.select(Some(selected as usize));
+    }
+
+    fn open_selected(&mut self) {
+        let Some(selected) = self.catalogue.selected() else {
+            return;
+        };
+        if let Some((i, Some(j))) = self.entries.get(selected).copied() {
+            if let Some(index) = self.chapters.iter().position(|e| *e == (i, j)) {
+                self.open(index, 0);
+                self.focus = Focus::Text;
+            }
+        }
+    }
+
+    /// 处理按键，返回是否退出
+    fn handle(&mut self, code: KeyCode) -> bool {
+        if let Some(search) = self.search.as_mut() {
+            match code {
+                KeyCode::Esc => self.search = None,
+                KeyCode::Enter => {
+                    self.query = self.search.take().unwrap();
+                    self.find();
+                }
+                KeyCode::Backspace => {
+                    search.pop();
+                }
+                KeyCode::Char(c) => search.push(c),
+                _ => (),
+            }
+            return false;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Catalogue => Focus::Text,
+                    Focus::Text => Focus::Catalogue,
+                }
+            }
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            KeyCode::Char('n') => self.next_match(),
+            KeyCode::Char('N') => self.prev_match(),
+            KeyCode::Char(']') => self.next_chapter(),
+            KeyCode::Char('[') => self.prev_chapter(),
+            KeyCode::Char('b') => {
+                self.mark();
+                self.status = match self.progress.save() {
+                    Ok(_) => String::from("Bookmark saved"),
+                    Err(err) => format!("Failed to save bookmark: {err}"),
+                };
+            }
+            _ if self.focus == Focus::Catalogue => match code {
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::PageUp => self.select(-(self.height as isize)),
+                KeyCode::PageDown => self.select(self.height as isize),
+                KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => self.open_selected(),
+                _ => (),
+            },
+            _ => match code {
+                KeyCode::Up | KeyCode::Char('k') => self.offset = self.offset.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') if self.offset + 1 < self.lines.len() => {
+                    self.offset += 1
+                }
+                KeyCode::PageDown | KeyCode::Right | KeyCode::Char(' ') | KeyCode::Char('l') => {
+                    self.next_page()
+                }
+                KeyCode::PageUp | KeyCode::Left | KeyCode::Char('h') => self.prev_page(),
+                _ => (),
+            },
+        }
+        false
+    }
+
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(f.size());
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .split(rows[0]);
+
+        let width = panes[1].width.saturating_sub(2) as usize;
+        self.height = panes[1].height.saturating_sub(2).max(1) as usize;
+        if width != self.width {
+            self.width = width;
+            self.wrap();
+        }
+
+        let focused = Style::default().fg(Color::Yellow);
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|(i, j)| match j {
+                None => ListItem::new(Span::styled(
+                    self.volumes[*i].title.to_owned(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Some(j) => {
+                    let chapter = &self.volumes[*i].chapters[*j];
+                    let title = if chapter.is_free {
+                        format!("  {}", chapter.title)
+                    } else {
+                        format!("  {} [VIP {}]", chapter.title, chapter.price)
+                    };
+                    ListItem::new(title)
+                }
+            })
+            .collect();
+        let catalogue = List::new(items)
+            .block(
+                Block::default()
+                    .title(self.novel_name.as_str())
+                    .borders(Borders::ALL)
+                    .border_style(if self.focus == Focus::Catalogue {
+                        focused
+                    } else {
+                        Style::default()
+                    }),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(catalogue, panes[0], &mut self.catalogue);
+
+        let title = self
+            .current
+            .map(|e| self.chapter_at(e).title.to_owned())
+            .unwrap_or_default();
+        let text: Vec<Spans> = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(self.height)
+            .map(|(i, e)| {
+                if self.matches.contains(&i) {
+                    Spans::from(Span::styled(e.text.as_str(), focused))
+                } else {
+                    Spans::from(e.text.as_str())
+                }
+            })
+            .collect();
+        let pages = self.lines.len().div_ceil(self.height).max(1);
+        let page = self.offset / self.height + 1;
+        let text = Paragraph::new(text).block(
+            Block::default()
+                .title(format!("{title} ({page}/{pages})"))
+                .borders(Borders::ALL)
+                .border_style(if self.focus == Focus::Text {
+                    focused
+                } else {
+                    Style::default()
+                }),
+        );
+        f.render_widget(text, panes[1]);
+
+        let status = match &self.search {
+            Some(search) => format!("/{search}"),
+            None => self.status.to_owned(),
+        };
+        f.render_widget(Paragraph::new(status), rows[1]);
+    }
+
+    fn run(&mut self, terminal: &mut Term) -> Result<()> {
+        loop {
+            terminal.draw(|f| self.draw(f))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && self.handle(key.code) {
+                    break;
+                }
+            }
+        }
+        self.mark();
+        self.progress.save()
+    }
+}
+
+fn split_paragraphs(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn handle_read(matches: &clap::ArgMatches) -> Result<()> {
+    if !Output::of(matches).is_table() {
+        bail!("the reader is interactive and only supports table output");
+    }
+    let proxy = match matches.get_one::<String>("user") {
+        Some(user) => login_as(Some(user))?,
+        None => match login_as(None) {
+            Ok(proxy) => proxy,
+            Err(_) => new_proxy()?,
+        },
+    };
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+
+    let mut reader = Reader::new(&proxy, novel_id)?;
+    if let Some(progress) = reader.progress.get(novel_id).cloned() {
+        reader.open_chapter(progress.chapter_id, progress.row);
+        reader.focus = Focus::Text;
+    }
+
+    with_terminal(|terminal| reader.run(terminal))
+}
@@ -0,0 +1,82 @@
+use super::{format_date, new_proxy, Output};
+use crate::{SearchFilter, SearchHit, SearchOrder, Type};
+
+use anyhow::Result;
+use colored::*;
+
+/// 每页的搜索结果数
+const PAGE_SIZE: usize = 20;
+
+/// 排序时最多获取的页数，排序只在这些结果中进行
+const SORTED_PAGES: usize = 10;
+
+pub fn handle_search(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = new_proxy()?;
+    let keyword = matches.get_one::<String>("KEYWORD").unwrap();
+    let limit = *matches.get_one::<usize>("limit").unwrap();
+    let filter = SearchFilter {
+        r#type: matches.get_one::<String>("type").map(|e| match e.as_str() {
+            "novel" => Type::Novel,
+            "comic" => Type::Comic,
+            "album" => Type::Album,
+            _ => unreachable!(),
+        }),
+        finished: matches.get_flag("finished"),
+        min_chars: *matches.get_one::<usize>("min-chars").unwrap(),
+    };
+    let order = matches.get_one::<String>("sort").map(|e| match e.as_str() {
+        "views" => SearchOrder::Views,
+        "marks" => SearchOrder::Marks,
+        "update" => SearchOrder::Update,
+        _ => unreachable!(),
+    });
+
+    // 排序时需要先获取足够多的结果，再从中取出排在最前的 `limit` 个
+    let mut hits: Vec<SearchHit> = vec![];
+    for page in 0.. {
+        let result = proxy.search(keyword, page, PAGE_SIZE)?;
+        let done = [
+            result.novels.len(),
+            result.comics.len(),
+            result.albums.len(),
+        ]
+        .iter()
+        .all(|e| *e < PAGE_SIZE);
+        hits.extend(result.parse()?.into_iter().filter(|e| filter.matches(e)));
+        let enough = match order {
+            Some(_) => page + 1 >= SORTED_PAGES.max(limit.div_ceil(PAGE_SIZE)),
+            None => hits.len() >= limit,
+        };
+        if done || enough {
+            break;
+        }
+    }
+    if let Some(order) = order {
+        order.sort(&mut hits);
+    }
+    hits.truncate(limit);
+
+    let output = match matches.get_one::<String>("format") {
+        Some(format) => Output::named(format),
+        None => Output::of(matches),
+    };
+    output.print(&hits, |hits| {
+        for hit in hits {
+            let chars = hit
+                .total_chars
+                .map_or(String::new(), |e| format!("  {e} chars"));
+            println!(
+                "{:>8}  {:<5}  {}{}{}  {} views  {}",
+                hit.id,
+                format!("{:?}", hit.r#type).to_lowercase(),
+                hit.name.bold(),
+                hit.author
+                    .as_ref()
+                    .map_or(String::new(), |e| format!(" ({e})")),
+                chars,
+                hit.total_views,
+                format_date(hit.last_update_time).dimmed(),
+            );
+        }
+    })
+}
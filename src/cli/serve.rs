@@ -0,0 +1,304 @@
+use super::{login_as, new_proxy};
+use crate::{Proxy, Value};
+
+use anyhow::{anyhow, bail, Result};
+use colored::*;
+use crypto::util::fixed_time_eq;
+use rand::{distributions::Alphanumeric, Rng};
+use reqwest::Url;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::Duration,
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// 带有 HTTP 状态码的错误
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+fn http_error(status: u16, message: &str) -> anyhow::Error {
+    anyhow::Error::new(HttpError {
+        status,
+        message: message.to_string(),
+    })
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// 响应缓存在代理缓存中的键前缀，与登录凭证等其它缓存项区分
+const CACHE_PREFIX: &str = "serve:";
+
+/// 每个代理缓存的响应数量上限，超出时移除最早的响应
+const MAX_CACHED_RESPONSES: usize = 256;
+
+struct Gateway {
+    token: String,                   //<! 访问令牌
+    user: Option<String>,            //<! 未指定账号时使用的默认账号
+    ttl: Duration,                   //<! 响应缓存的有效期
+    guest: Proxy,                    //<! 游客身份的代理
+    proxies: HashMap<String, Proxy>, //<! 已登录账号的代理
+}
+
+/// 缓存项的写入时间（秒）
+fn cached_at(value: &Value) -> u64 {
+    value["time"].as_u64().unwrap_or_default()
+}
+
+/// 读取代理缓存中未过期的响应
+fn load_cached(proxy: &Proxy, key: &str, ttl: Duration) -> Option<Value> {
+    let cached = proxy.load(&format!("{CACHE_PREFIX}{key}"))?;
+    let now = Proxy::timestamp().as_secs();
+    (now.saturating_sub(cached_at(cached)) < ttl.as_secs()).then(|| cached["value"].to_owned())
+}
+
+/// 将响应写入代理缓存，同时移除已过期的响应，数量超出上限时移除最早的响应
+fn store_cached(proxy: &mut Proxy, key: &str, value: Value, ttl: Duration) {
+    let now = Proxy::timestamp().as_secs();
+    let mut cached: Vec<(u64, String)> = vec![];
+    proxy.retain(|key, value| {
+        if !key.starts_with(CACHE_PREFIX) {
+            return true;
+        }
+        let time = cached_at(value);
+        if now.saturating_sub(time) >= ttl.as_secs() {
+            return false;
+        }
+        cached.push((time, key.to_owned()));
+        true
+    });
+    if cached.len() >= MAX_CACHED_RESPONSES {
+        cached.sort();
+        for (_, key) in &cached[..=cached.len() - MAX_CACHED_RESPONSES] {
+            proxy.remove(key);
+        }
+    }
+
+    proxy.store(
+        &format!("{CACHE_PREFIX}{key}"),
+        json!({ "time": now, "value": value }),
+    );
+}
+
+/// 代理是否已登录，未登录时以 401 响应
+fn authorized(proxy: &Proxy) -> Result<&Proxy> {
+    if !proxy.is_authenticated() {
+        return Err(http_error(401, "the route requires a user"));
+    }
+    Ok(proxy)
+}
+
+/// 分发请求，`segments` 为路径中的各段
+fn route(proxy: &Proxy, segments: &[&str], query: &BTreeMap<String, String>) -> Result<Value> {
+    let id = |segment: &str| {
+        segment
+            .parse::<i32>()
+            .map_err(|_| http_error(400, &format!("invalid id: {segment}")))
+    };
+    let value = match segments {
+        ["novels", novel_id] => {
+            let novel = proxy.novel_info(id(novel_id)?)?.parse()?;
+            serde_json::to_value(novel)?
+        }
+        ["novels", novel_id, "catalogue"] => {
+            let volumes = proxy.catalogue_of(id(novel_id)?)?.parse()?;
+            serde_json::to_value(volumes)?
+        }
+        ["chapters", chapter_id] => {
+            let chapter = proxy.chapter_content(id(chapter_id)?)?;
+            json!({
+                "id": chapter.chapId,
+                "novel_id": chapter.novelId,
+                "volume_id": chapter.volumeId,
+                "title": chapter.title,
+                "total_chars": chapter.charCount,
+                "is_vip": chapter.isVip,
+                "content": chapter.expand.and_then(|e| e.content),
+            })
+        }
+        ["search"] => {
+            let Some(keyword) = query.get("q") else {
+                return Err(http_error(400, "missing query parameter: q"));
+            };
+            let number = |key: &str, default: usize| match query.get(key) {
+                Some(value) => value
+                    .parse::<usize>()
+                    .map_err(|_| http_error(400, &format!("invalid {key}: {value}"))),
+                None => Ok(default),
+            };
+            let page = number("page", 0)?;
+            let size = number("size", 20)?;
+            let hits = proxy.search(keyword, page, size)?.parse()?;
+            serde_json::to_value(hits)?
+        }
+        ["users", user_id] => {
+            let info = proxy.user_info(id(user_id)?)?.parse()?;
+            serde_json::to_value(info)?
+        }
+        ["me"] => {
+            let proxy = authorized(proxy)?;
+            let profile = proxy.profile()?.parse(proxy.money()?)?;
+            serde_json::to_value(profile)?
+        }
+        ["pockets"] => {
+            let mut pockets = vec![];
+            for pocket in authorized(proxy)?.favoirtes()? {
+                pockets.push(pocket.parse()?);
+            }
+            serde_json::to_value(pockets)?
+        }
+        _ => return Err(http_error(404, "not found")),
+    };
+    Ok(value)
+}
+
+impl Gateway {
+    /// 获取账号的代理，凭证过期时重新登录，未指定账号时使用游客身份
+    fn proxy_of(&mut self, user: Option<&String>) -> Result<&mut Proxy> {
+        let Some(user) = user.or(self.user.as_ref()).cloned() else {
+            return Ok(&mut self.guest);
+        };
+        if !self.proxies.get(&user).is_some_and(Proxy::is_authenticated) {
+            let proxy = login_as(Some(&user)).map_err(|err| http_error(401, &err.to_string()))?;
+            self.proxies.insert(user.to_owned(), proxy);
+        }
+        Ok(self.proxies.get_mut(&user).unwrap())
+    }
+
+    /// 处理请求，返回状态码和响应体
+    fn handle(&mut self, request: &Request) -> (u16, Value) {
+        match self.try_handle(request) {
+            Ok(value) => (200, value),
+            Err(err) => {
+                let status = err.downcast_ref::<HttpError>().map_or(502, |e| e.status);
+                (status, json!({ "error": err.to_string() }))
+            }
+        }
+    }
+
+    fn try_handle(&mut self, request: &Request) -> Result<Value> {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|e| e.field.equiv(name))
+                .map(|e| e.value.to_string())
+        };
+        let url = Url::parse(&format!("http://localhost{}", request.url()))?;
+        let mut query: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+
+        let token = query.remove("token").or_else(|| {
+            header("Authorization").and_then(|e| e.strip_prefix("Bearer ").map(str::to_string))
+        });
+        // 以固定时间比较令牌，避免通过响应时间逐字节猜测
+        let token = token.unwrap_or_default();
+        if !fixed_time_eq(token.as_bytes(), self.token.as_bytes()) {
+            return Err(http_error(401, "invalid access token"));
+        }
+        if *request.method() != Method::Get {
+            return Err(http_error(405, "method not allowed"));
+        }
+
+        let user = query.remove("user").or_else(|| header("X-Sfutils-User"));
+        let segments: Vec<&str> = url.path().split('/').filter(|e| !e.is_empty()).collect();
+        let key = format!("{}?{query:?}", url.path());
+        let ttl = self.ttl;
+        let proxy = self.proxy_of(user.as_ref())?;
+        if let Some(value) = load_cached(proxy, &key, ttl) {
+            return Ok(value);
+        }
+
+        let value = route(proxy, &segments, &query)?;
+        if !ttl.is_zero() {
+            store_cached(proxy, &key, value.to_owned(), ttl);
+        }
+        Ok(value)
+    }
+}
+
+pub fn handle_serve(matches: &clap::ArgMatches) -> Result<()> {
+    let listen = matches.get_one::<String>("listen").unwrap();
+    let token = match matches.get_one::<String>("token") {
+        Some(token) => token.to_owned(),
+        None => {
+            let token: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .map(char::from)
+                .collect();
+            eprintln!("{}: {token}", "Access token".bold());
+            token
+        }
+    };
+    if token.is_empty() {
+        bail!("the access token must not be empty");
+    }
+    let mut gateway = Gateway {
+        token,
+        user: matches.get_one::<String>("user").cloned(),
+        ttl: Duration::from_secs(*matches.get_one::<u64>("cache-ttl").unwrap()),
+        guest: new_proxy()?,
+        proxies: HashMap::new(),
+    };
+    if let Some(user) = &gateway.user {
+        let proxy = login_as(Some(user))?;
+        gateway.proxies.insert(user.to_owned(), proxy);
+    }
+
+    let server =
+        Server::http(listen).map_err(|err| anyhow!("failed to listen on {listen}: {err}"))?;
+    eprintln!("{} http://{listen}", "Listening on".bold());
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    for request in server.incoming_requests() {
+        let (status, value) = gateway.handle(&request);
+        // 查询参数中可能包含访问令牌，日志只记录路径
+        let path = request.url().split('?').next().unwrap_or_default();
+        eprintln!("{} {path} {status}", request.method());
+        let response = Response::from_string(value.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("{}: {err}", "Failed to respond".bold().red());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_responses_are_capped() {
+        let mut proxy = Proxy::default();
+        let ttl = Duration::from_secs(60);
+        for i in 0..MAX_CACHED_RESPONSES + 10 {
+            store_cached(&mut proxy, &format!("/novels/{i}"), json!(i), ttl);
+        }
+        assert_eq!(load_cached(&proxy, "/novels/1", ttl), None);
+        let last = MAX_CACHED_RESPONSES + 9;
+        assert_eq!(
+            load_cached(&proxy, &format!("/novels/{last}"), ttl),
+            Some(json!(last))
+        );
+
+        let mut count = 0;
+        proxy.retain(|key, _| {
+            count += key.starts_with(CACHE_PREFIX) as usize;
+            true
+        });
+        assert_eq!(count, MAX_CACHED_RESPONSES);
+    }
+}
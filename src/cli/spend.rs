@@ -0,0 +1,83 @@
+use super::{format_unix_date, login_as, Output};
+use crate::{Ledger, LedgerEntry};
+
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct Balance {
+    fire_coins: usize, //<! 剩余火卷
+    vouchers: usize,   //<! 剩余代金卷
+    used: usize,       //<! 累计消费的火卷
+}
+
+/// 消费报告
+#[derive(Debug, Serialize)]
+struct SpendReport<'a> {
+    entries: &'a [LedgerEntry],
+    total_fire_coins: usize,
+    total_vouchers: usize,
+    balance: Balance,
+}
+
+pub fn handle_spend(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = login_as(matches.get_one::<String>("user"))?;
+    let novel_id = matches.get_one::<i32>("novel").copied();
+    let since = match matches.get_one::<String>("since") {
+        Some(date) => Some(date.parse::<dateparser::DateTimeUtc>()?.0.timestamp()),
+        None => None,
+    };
+
+    let records = proxy
+        .consume_history(&[], novel_id)?
+        .into_iter()
+        .map(|e| e.parse())
+        .collect::<Result<Vec<_>>>()?;
+    let ledger = Ledger::aggregate(records, since);
+    let money = proxy.money()?;
+
+    let report = SpendReport {
+        entries: &ledger.entries,
+        total_fire_coins: ledger.total_fire_coins(),
+        total_vouchers: ledger.total_vouchers(),
+        balance: Balance {
+            fire_coins: money.fireMoneyRemain,
+            vouchers: money.couponsRemain,
+            used: money.fireMoneyUsed,
+        },
+    };
+    Output::of(matches).print(&report, |_| {
+        for entry in &ledger.entries {
+            let name = entry.novel_name.clone().unwrap_or_default();
+            println!(
+                "{} ({} {}) {} fire coins, {} vouchers in {} purchases [{} ~ {}]",
+                name.bold(),
+                entry.r#type,
+                entry.novel_id,
+                entry.total_fire_coins.to_string().yellow(),
+                entry.total_vouchers,
+                entry.total_items,
+                format_unix_date(entry.first_time),
+                format_unix_date(entry.last_time),
+            );
+            for (month, fire_coins) in &entry.monthly {
+                println!("    {month}  {fire_coins}");
+            }
+        }
+
+        println!(
+            "{}: {} fire coins, {} vouchers",
+            "Total".bold(),
+            ledger.total_fire_coins().to_string().yellow(),
+            ledger.total_vouchers(),
+        );
+        println!(
+            "{}: {} fire coins, {} vouchers ({} fire coins used in all)",
+            "Balance".bold(),
+            money.fireMoneyRemain,
+            money.couponsRemain,
+            money.fireMoneyUsed,
+        );
+    })
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use crossterm::{
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, Stdout};
+use tui::{backend::CrosstermBackend, Terminal};
+
+pub type Term = Terminal<CrosstermBackend<Stdout>>;
+
+/// 在全屏终端中运行，结束时恢复终端状态
+pub fn with_terminal<T>(f: impl FnOnce(&mut Term) -> Result<T>) -> Result<T> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = f(&mut terminal);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+/// 终端中字符的显示宽度
+#[inline]
+pub fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        1
+    } else {
+        2
+    }
+}
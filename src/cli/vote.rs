@@ -0,0 +1,68 @@
+use super::{confirm, login_as, Output};
+use crate::api::action::ActionError;
+
+use anyhow::{bail, Result};
+use colored::*;
+use serde_json::json;
+
+pub fn handle_vote(matches: &clap::ArgMatches) -> Result<()> {
+    let proxy = login_as(matches.get_one::<String>("user"))?;
+    let novel_id = *matches.get_one::<i32>("NOVEL").unwrap();
+    let limit = *matches.get_one::<usize>("limit").unwrap();
+    let dry_run = matches.get_flag("dry-run");
+
+    let (amount, action, check) = if let Some(num) = matches.get_one::<usize>("tickets") {
+        let check = proxy.check_vote(*num, limit);
+        (*num, format!("vote {num} monthly tickets"), check)
+    } else {
+        let amount = *matches.get_one::<usize>("bonus").unwrap();
+        let check = proxy.check_bonus(amount, limit);
+        (amount, format!("tip {amount} fire coins"), check)
+    };
+
+    if let Err(err) = check {
+        if err.is::<ActionError>() {
+            let hint = "Action refused";
+            eprintln!("{}: {err}", hint.bold().red());
+        }
+        return Err(err);
+    }
+
+    let output = Output::of(matches);
+    let kind = if matches.contains_id("tickets") {
+        "ticket"
+    } else {
+        "bonus"
+    };
+    let report =
+        |done: bool| json!({ "novel_id": novel_id, "kind": kind, "amount": amount, "done": done });
+
+    if dry_run {
+        return output.print(&report(false), |_| {
+            println!("Would {action} to {}", novel_id.to_string().bold())
+        });
+    }
+
+    // 投票与打赏的接口未经抓包验证，需显式确认后才实际发送
+    if !matches.get_flag("experimental") {
+        let hint = "Action refused";
+        eprintln!(
+            "{}: the ticket and bonus endpoints are unverified, pass --experimental to send anyway",
+            hint.bold().red()
+        );
+        bail!(hint.to_lowercase());
+    }
+
+    if !matches.get_flag("yes") && !confirm(&format!("{action} to {novel_id}?"))? {
+        return output.print(&report(false), |_| ());
+    }
+
+    if matches.contains_id("tickets") {
+        proxy.vote_tickets(novel_id, amount)?;
+    } else {
+        proxy.send_bonus(novel_id, amount)?;
+    }
+    output.print(&report(true), |_| {
+        println!("Done, {action} to {}", novel_id.to_string().bold())
+    })
+}
@@ -0,0 +1,263 @@
+use super::{login_as, resolve_user, Output};
+use crate::{
+    api::types,
+    local::{parse_interval, NovelSnapshot, ShelfSnapshot},
+    notify::{CommandSink, JsonlSink, Sink, StdoutSink, WebhookSink},
+    to_timestamp, NewChapter, NovelUpdate, Proxy, Volume,
+};
+
+use anyhow::Result;
+use colored::*;
+use std::{collections::BTreeSet, path::PathBuf, thread, time::Duration};
+
+/// 小说的更新时间与字数是否与快照一致，一致时无需获取目录
+fn is_unchanged(novel: &types::NovelRef, snapshot: &NovelSnapshot) -> Result<bool> {
+    let last_update_time = to_timestamp(&novel.lastUpdateTime)?;
+    Ok(snapshot.last_update_time == last_update_time && snapshot.char_count == novel.charCount)
+}
+
+/// 比较小说的目录与快照，返回新的快照以及需要通知的更新
+fn diff(
+    novel: &types::NovelRef,
+    volumes: &[Volume],
+    snapshot: Option<&NovelSnapshot>,
+) -> Result<(NovelSnapshot, Option<NovelUpdate>)> {
+    let last_update_time = to_timestamp(&novel.lastUpdateTime)?;
+    let current = NovelSnapshot {
+        name: novel.novelName.to_owned(),
+        last_update_time,
+        char_count: novel.charCount,
+        chapters: volumes
+            .iter()
+            .flat_map(|e| &e.chapters)
+            .map(|e| e.id)
+            .collect(),
+    };
+    // 首次见到的小说只建立快照
+    let Some(snapshot) = snapshot else {
+        return Ok((current, None));
+    };
+
+    let new_chapters: Vec<NewChapter> = volumes
+        .iter()
+        .flat_map(|volume| volume.chapters.iter().map(move |e| (volume, e)))
+        .filter(|(_, e)| !snapshot.chapters.contains(&e.id))
+        .map(|(volume, e)| NewChapter {
+            id: e.id,
+            title: e.title.to_owned(),
+            volume: volume.title.to_owned(),
+            is_free: e.is_free,
+            creation_time: e.creation_time,
+        })
+        .collect();
+    let added_chars = novel.charCount as isize - snapshot.char_count as isize;
+    if new_chapters.is_empty() && added_chars == 0 {
+        return Ok((current, None));
+    }
+
+    let update = NovelUpdate {
+        novel_id: novel.novelId,
+        novel_name: novel.novelName.to_owned(),
+        author: novel.authorName.to_owned(),
+        last_update_time,
+        total_chars: novel.charCount,
+        added_chars,
+        new_chapters,
+    };
+    Ok((current, Some(update)))
+}
+
+/// 获取目录并与快照比较，更新时间与字数未变化时沿用快照
+fn check_novel(
+    proxy: &Proxy,
+    novel: &types::NovelRef,
+    snapshot: Option<&NovelSnapshot>,
+) -> Result<(NovelSnapshot, Option<NovelUpdate>)> {
+    if let Some(snapshot) = snapshot {
+        if is_unchanged(novel, snapshot)? {
+            return Ok((snapshot.clone(), None));
+        }
+    }
+    let volumes = proxy.catalogue_of(novel.novelId)?.parse()?;
+    diff(novel, &volumes, snapshot)
+}
+
+/// 检查一遍书架，返回自上次快照以来的更新
+///
+/// 快照只在内存中更新，由调用方在发送通知后保存，进程在通知前退出时下次检查仍会通知这些更新
+pub(super) fn check(
+    user: Option<&String>,
+    snapshot: &mut ShelfSnapshot,
+) -> Result<Vec<NovelUpdate>> {
+    let proxy = login_as(user)?;
+    let mut updates = vec![];
+    let mut novels = BTreeSet::new();
+    for pocket in proxy.favoirtes()? {
+        for novel in pocket.expand.and_then(|e| e.novels).unwrap_or_default() {
+            novels.insert(novel.novelId);
+            match check_novel(&proxy, &novel, snapshot.get(novel.novelId)) {
+                Ok((current, update)) => {
+                    snapshot.insert(novel.novelId, current);
+                    updates.extend(update);
+                }
+                Err(err) => eprintln!(
+                    "{}: {}: {err}",
+                    "Failed to check".bold().red(),
+                    novel.novelName
+                ),
+            }
+        }
+    }
+    snapshot.retain(&novels);
+    Ok(updates)
+}
+
+/// 将更新发送到所有通知方式，单个通知方式失败时只输出错误
+pub(super) fn notify_all(sinks: &mut [Box<dyn Sink>], updates: &[NovelUpdate]) {
+    for update in updates {
+        for sink in sinks.iter_mut() {
+            if let Err(err) = sink.notify(update) {
+                eprintln!("{}: {err}", "Failed to notify".bold().red());
+            }
+        }
+    }
+}
+
+pub fn handle_watch(matches: &clap::ArgMatches) -> Result<()> {
+    let user = matches.get_one::<String>("user");
+    let interval = parse_interval(matches.get_one::<String>("interval").unwrap())?;
+    let once = matches.get_flag("once");
+
+    let mut sinks: Vec<Box<dyn Sink>> = vec![];
+    if !matches.get_flag("quiet") {
+        let json = !Output::of(matches).is_table();
+        sinks.push(Box::new(StdoutSink { json }));
+    }
+    for command in matches.get_many::<String>("exec").unwrap_or_default() {
+        sinks.push(Box::new(CommandSink {
+            command: command.to_owned(),
+        }));
+    }
+    for url in matches.get_many::<String>("webhook").unwrap_or_default() {
+        sinks.push(Box::new(WebhookSink::new(url)));
+    }
+    if let Some(path) = matches.get_one::<String>("log") {
+        sinks.push(Box::new(JsonlSink {
+            path: PathBuf::from(path),
+        }));
+    }
+
+    let mut snapshot = ShelfSnapshot::open(&resolve_user(user)?)?;
+    loop {
+        match check(user, &mut snapshot) {
+            Ok(updates) => {
+                notify_all(&mut sinks, &updates);
+                snapshot.save()?;
+            }
+            Err(err) if once => return Err(err),
+            Err(err) => eprintln!("{}: {err}", "Failed to check the bookshelf".bold().red()),
+        }
+        if once {
+            break;
+        }
+        thread::sleep(Duration::from_secs(interval as u64));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chapter, Id};
+
+    fn novel(last_update_time: &str, char_count: usize) -> types::NovelRef {
+        types::NovelRef {
+            allowDown: true,
+            authorId: 2,
+            authorName: "作者".to_string(),
+            bgBanner: String::new(),
+            categoryId: 0,
+            charCount: char_count,
+            isFinish: false,
+            isSensitive: false,
+            isSticky: false,
+            lastUpdateTime: last_update_time.to_string(),
+            markCount: 0,
+            markDateTime: String::new(),
+            novelCover: String::new(),
+            novelId: 1,
+            novelName: "小说".to_string(),
+            point: 0.0,
+            signStatus: String::new(),
+            stickyDateTime: None,
+            typeId: 0,
+            viewTimes: 0,
+            expand: None,
+        }
+    }
+
+    fn volume(chapters: &[Id]) -> Volume {
+        Volume {
+            novel_id: 1,
+            id: 10,
+            title: "第一卷".to_string(),
+            order: 1,
+            chapters: chapters
+                .iter()
+                .map(|id| Chapter {
+                    novel_id: 1,
+                    volume_id: 10,
+                    id: *id,
+                    title: format!("第{id}章"),
+                    order: *id as usize,
+                    total_chars: 1000,
+                    creation_time: 0,
+                    update_time: 0,
+                    is_free: true,
+                    price: 0,
+                    origin_price: 0,
+                    content: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn first_seen_novel_only_builds_snapshot() {
+        let novel = novel("2023-05-20T08:00:00", 2000);
+        let (snapshot, update) = diff(&novel, &[volume(&[1, 2])], None).unwrap();
+        assert!(update.is_none());
+        assert_eq!(snapshot.char_count, 2000);
+        assert_eq!(snapshot.chapters.into_iter().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn new_chapters_are_reported() {
+        let (snapshot, _) = diff(
+            &novel("2023-05-20T08:00:00", 2000),
+            &[volume(&[1, 2])],
+            None,
+        )
+        .unwrap();
+
+        let novel = novel("2023-05-21T08:00:00", 3000);
+        assert!(!is_unchanged(&novel, &snapshot).unwrap());
+        let (current, update) = diff(&novel, &[volume(&[1, 2, 3])], Some(&snapshot)).unwrap();
+        let update = update.unwrap();
+        assert_eq!(update.added_chars, 1000);
+        assert_eq!(update.new_chapters.len(), 1);
+        assert_eq!(update.new_chapters[0].id, 3);
+        assert_eq!(update.new_chapters[0].volume, "第一卷");
+        assert!(current.chapters.contains(&3));
+    }
+
+    #[test]
+    fn unchanged_novel_is_not_reported() {
+        let novel = novel("2023-05-20T08:00:00", 2000);
+        let (snapshot, _) = diff(&novel, &[volume(&[1, 2])], None).unwrap();
+        assert!(is_unchanged(&novel, &snapshot).unwrap());
+        let (_, update) = diff(&novel, &[volume(&[1, 2])], Some(&snapshot)).unwrap();
+        assert!(update.is_none());
+    }
+}
@@ -0,0 +1,110 @@
+//! 小说下载
+
+use crate::{
+    local::{BookStore, Library},
+    Id, Proxy, Timestamp,
+};
+
+use anyhow::Result;
+use std::{collections::HashSet, sync::mpsc, thread, time::SystemTime};
+
+pub struct Downloader<'a> {
+    proxy: &'a Proxy,
+    concurrency: usize, //<! 同时下载章节的线程数
+}
+
+impl<'a> Downloader<'a> {
+    pub fn new(proxy: &'a Proxy) -> Self {
+        Self {
+            proxy,
+            concurrency: 4,
+        }
+    }
+
+    pub fn with_concurrency(mut self, n: usize) -> Self {
+        self.concurrency = n.max(1);
+        self
+    }
+
+    /// 下载章节内容并写入书库，返回成功下载的章节数
+    fn download_chapters(&self, library: &Library, chapters: &[Id]) -> Result<usize> {
+        let size = chapters.len().div_ceil(self.concurrency).max(1);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for chunk in chapters.chunks(size) {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    for chapter_id in chunk {
+                        let content = self
+                            .proxy
+                            .chapter_content(*chapter_id)
+                            .ok()
+                            .and_then(|e| e.expand)
+                            .and_then(|e| e.content)
+                            .unwrap_or_default();
+                        if !content.is_empty() && sender.send((*chapter_id, content)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            // 书库的连接不能跨线程共享，由当前线程逐个写入
+            let mut count = 0;
+            for (chapter_id, content) in receiver {
+                library.put_content(chapter_id, &content, now()?)?;
+                count += 1;
+            }
+            Ok(count)
+        })
+    }
+
+    /// 增量下载小说到下载目录，更新元数据与封面，未登录时只下载免费章节，返回新保存的章节数
+    ///
+    /// 小说目录下旧格式的章节文件 `<章节 ID>.txt` 直接导入书库，不再重新下载
+    pub fn download(&self, store: &BookStore, novel_id: Id) -> Result<usize> {
+        let novel = self.proxy.novel_info(novel_id)?.parse()?;
+        let volumes = self.proxy.catalogue_of(novel_id)?.parse()?;
+        let mut library = store.library()?;
+        library.upsert(&novel, &volumes, now()?)?;
+
+        if !store.has_cover(novel_id) && !novel.cover.is_empty() {
+            // 封面下载失败不影响章节的下载
+            if let Ok(data) = reqwest::blocking::get(&novel.cover).and_then(|e| e.bytes()) {
+                store.save_cover(novel_id, &data)?;
+            }
+        }
+
+        let cache = store.cache_of(novel_id)?;
+        let mut imported = 0;
+        let mut missing = vec![];
+        for chapter_id in library.missing_chapters(novel_id)? {
+            match cache.get(chapter_id).filter(|e| !e.is_empty()) {
+                Some(content) => {
+                    library.put_content(chapter_id, &content, now()?)?;
+                    imported += 1;
+                }
+                None => missing.push(chapter_id),
+            }
+        }
+
+        let authenticated = self.proxy.is_authenticated();
+        let free: HashSet<Id> = volumes
+            .iter()
+            .flat_map(|e| &e.chapters)
+            .filter(|e| e.is_free)
+            .map(|e| e.id)
+            .collect();
+        let chapters: Vec<Id> = missing
+            .into_iter()
+            .filter(|e| authenticated || free.contains(e))
+            .collect();
+        Ok(imported + self.download_chapters(&library, &chapters)?)
+    }
+}
+
+fn now() -> Result<Timestamp> {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    Ok(now.as_secs() as Timestamp)
+}
@@ -0,0 +1,175 @@
+//! 由已下载的小说生成 TXT 与 EPUB 电子书
+
+use crate::{
+    feed::escape,
+    local::{Library, LocalBook},
+};
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use std::{
+    fmt::Write as _,
+    io::{Cursor, Write},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+fn paragraphs_of(content: &str) -> impl Iterator<Item = &str> {
+    content.lines().map(str::trim).filter(|e| !e.is_empty())
+}
+
+/// 由书库中的内容生成 TXT 文本，缺失内容的章节被跳过
+pub fn build_txt(book: &LocalBook, library: &Library) -> Result<String> {
+    let mut text = String::new();
+    writeln!(text, "{}\n{}\n", book.name, book.author)?;
+    for volume in &book.volumes {
+        writeln!(text, "{}\n", volume.title)?;
+        for chapter in &volume.chapters {
+            let Some(content) = library.content_of(chapter.id)? else {
+                continue;
+            };
+            writeln!(text, "{}\n", chapter.title)?;
+            for paragraph in paragraphs_of(&content) {
+                writeln!(text, "{paragraph}")?;
+            }
+            text.push('\n');
+        }
+    }
+    Ok(text)
+}
+
+fn xhtml(title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="zh-CN">
+<head><title>{}</title></head>
+<body>
+{body}</body>
+</html>
+"#,
+        escape(title)
+    )
+}
+
+/// 生成 EPUB 3 电子书，每个章节为一个 XHTML 文件，缺失内容的章节被跳过
+pub fn build_epub(book: &LocalBook, library: &Library, cover: Option<&[u8]>) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+    // mimetype 必须是第一个且不压缩的文件
+    zip.start_file("mimetype", options)?;
+    zip.write_all(b"application/epub+zip")?;
+    zip.start_file("META-INF/container.xml", options)?;
+    zip.write_all(
+        br#"<?xml version="1.0" encoding="utf-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#,
+    )?;
+
+    let mut manifest = String::new();
+    let mut spine = String::new();
+    let mut nav = String::new();
+    if let Some(cover) = cover {
+        zip.start_file("OEBPS/cover.jpg", options)?;
+        zip.write_all(cover)?;
+        writeln!(
+            manifest,
+            r#"    <item id="cover" href="cover.jpg" media-type="image/jpeg" properties="cover-image"/>"#
+        )?;
+    }
+
+    let mut intro = format!(
+        "<h1>{}</h1>\n<p>{}</p>\n",
+        escape(&book.name),
+        escape(&book.author)
+    );
+    for paragraph in paragraphs_of(&book.intro) {
+        writeln!(intro, "<p>{}</p>", escape(paragraph))?;
+    }
+    zip.start_file("OEBPS/intro.xhtml", options)?;
+    zip.write_all(xhtml(&book.name, &intro).as_bytes())?;
+    writeln!(
+        manifest,
+        r#"    <item id="intro" href="intro.xhtml" media-type="application/xhtml+xml"/>"#
+    )?;
+    writeln!(spine, r#"    <itemref idref="intro"/>"#)?;
+
+    for volume in &book.volumes {
+        let mut items = String::new();
+        for chapter in &volume.chapters {
+            let Some(content) = library.content_of(chapter.id)? else {
+                continue;
+            };
+            let mut body = format!("<h2>{}</h2>\n", escape(&chapter.title));
+            for paragraph in paragraphs_of(&content) {
+                writeln!(body, "<p>{}</p>", escape(paragraph))?;
+            }
+            let href = format!("c{}.xhtml", chapter.id);
+            zip.start_file(format!("OEBPS/{href}"), options)?;
+            zip.write_all(xhtml(&chapter.title, &body).as_bytes())?;
+            writeln!(
+                manifest,
+                r#"    <item id="c{}" href="{href}" media-type="application/xhtml+xml"/>"#,
+                chapter.id
+            )?;
+            writeln!(spine, r#"    <itemref idref="c{}"/>"#, chapter.id)?;
+            writeln!(
+                items,
+                r#"<li><a href="{href}">{}</a></li>"#,
+                escape(&chapter.title)
+            )?;
+        }
+        if !items.is_empty() {
+            writeln!(
+                nav,
+                "<li><span>{}</span><ol>\n{items}</ol></li>",
+                escape(&volume.title)
+            )?;
+        }
+    }
+
+    let nav = format!(r#"<nav epub:type="toc"><h1>目录</h1><ol>{nav}</ol></nav>"#);
+    zip.start_file("OEBPS/nav.xhtml", options)?;
+    zip.write_all(xhtml("目录", &nav).as_bytes())?;
+
+    let modified = Utc
+        .timestamp_opt(book.last_update_time, 0)
+        .single()
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ");
+    let mut subjects = String::new();
+    for tag in &book.tags {
+        writeln!(subjects, "    <dc:subject>{}</dc:subject>", escape(tag))?;
+    }
+    let opf = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="book-id">urn:sfacg:novel:{}</dc:identifier>
+    <dc:title>{}</dc:title>
+    <dc:creator>{}</dc:creator>
+    <dc:language>zh-CN</dc:language>
+    <dc:description>{}</dc:description>
+{subjects}    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+{manifest}  </manifest>
+  <spine>
+{spine}  </spine>
+</package>
+"#,
+        book.id,
+        escape(&book.name),
+        escape(&book.author),
+        escape(&book.intro),
+    );
+    zip.start_file("OEBPS/content.opf", options)?;
+    zip.write_all(opf.as_bytes())?;
+
+    Ok(zip.finish()?.into_inner())
+}
@@ -0,0 +1,215 @@
+//! 小说文本导出
+
+use crate::{local::Library, Annotations, Barrages, Chapter, Id, Proxy};
+
+use anyhow::Result;
+use serde::Serialize;
+use std::{fmt::Write, thread};
+
+/// 获取小说弹幕时最多请求的页数，避免服务端持续返回满页时无限请求
+const MAX_BARRAGE_PAGES: usize = 100;
+
+/// 导出的文本
+#[derive(Debug, Default, Serialize)]
+pub struct ExportedText {
+    pub text: String,                //<! 导出的文本
+    pub locked_chapters: Vec<Id>,    //<! 未解锁而未能导出内容的章节
+    pub unaligned_chapters: Vec<Id>, //<! 段落数与服务端行数不一致而未内联段评的章节
+}
+
+pub struct TextExporter<'a> {
+    proxy: &'a Proxy,
+    library: Option<&'a Library>, //<! 优先从中读取章节内容的书库
+    annotations: usize,           //<! 每个段落内联的段评数量
+    barrages: bool,               //<! 是否在章节末尾附上弹幕
+    concurrency: usize,           //<! 同时获取段评的线程数
+}
+
+impl<'a> TextExporter<'a> {
+    pub fn new(proxy: &'a Proxy) -> Self {
+        Self {
+            proxy,
+            library: None,
+            annotations: 0,
+            barrages: false,
+            concurrency: 4,
+        }
+    }
+
+    /// 以脚注的形式为每个段落内联点赞数最高的前 `n` 条段评
+    ///
+    /// 段评只能按段落获取，每个章节需要发起与段落数相同的请求
+    pub fn with_annotations(mut self, n: usize) -> Self {
+        self.annotations = n;
+        self
+    }
+
+    /// 在每个章节的末尾按发送时间附上章节的弹幕，不属于任何章节的弹幕不导出
+    pub fn with_barrages(mut self, barrages: bool) -> Self {
+        self.barrages = barrages;
+        self
+    }
+
+    /// 优先使用书库中已保存的章节内容，书库中没有的章节仍从服务端获取
+    ///
+    /// 目录总是从服务端获取；内联段评时仍需从服务端获取章节的行数
+    pub fn with_library(mut self, library: &'a Library) -> Self {
+        self.library = Some(library);
+        self
+    }
+
+    pub fn with_concurrency(mut self, n: usize) -> Self {
+        self.concurrency = n.max(1);
+        self
+    }
+
+    /// 并发获取章节第 1 到第 `rows` 行的段评，丢弃服务端返回的不属于所请求行的段评
+    fn annotations_of(&self, chapter_id: Id, rows: usize) -> Result<Annotations> {
+        let rows: Vec<usize> = (1..=rows).collect();
+        let size = rows.len().div_ceil(self.concurrency).max(1);
+        // 书库的连接不能跨线程共享，线程中只使用代理
+        let (proxy, n) = (self.proxy, self.annotations);
+        let batches = thread::scope(|scope| {
+            let handles: Vec<_> = rows
+                .chunks(size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut tsukkomis = vec![];
+                        for row in chunk {
+                            let items = proxy.tsukkomis_of(chapter_id, *row, "hot", 0, n)?;
+                            tsukkomis.extend(items.into_iter().filter(|e| e.row == *row));
+                        }
+                        anyhow::Ok(tsukkomis)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|e| e.join().unwrap())
+                .collect::<Result<Vec<_>>>()
+        })?;
+
+        let mut annotations = Annotations::default();
+        for tsukkomi in batches.into_iter().flatten() {
+            annotations.insert(tsukkomi.parse()?);
+        }
+        Ok(annotations)
+    }
+
+    /// 获取小说的全部弹幕，最多获取 `MAX_BARRAGE_PAGES` 页
+    fn barrages_of(&self, novel_id: Id) -> Result<Barrages> {
+        let size = 60;
+        let mut barrages = Barrages::default();
+        for page in 0..MAX_BARRAGE_PAGES {
+            let items = self.proxy.barrages_of(novel_id, 3, false, page, size)?;
+            let done = items.len() < size;
+            for barrage in items {
+                barrages.insert(barrage.parse()?);
+            }
+            if done {
+                return Ok(barrages);
+            }
+        }
+        eprintln!(
+            "warning: barrages of {novel_id} are truncated to the first {MAX_BARRAGE_PAGES} pages"
+        );
+        Ok(barrages)
+    }
+
+    /// 导出章节文本，章节未解锁时只写入标题并记录在 `locked_chapters` 中
+    ///
+    /// 段评以去除空行后的段落序号（从 1 开始）对应服务端的行号，
+    /// 段落数与服务端给出的行数 `rowNum` 不一致时无法确定对应关系，不内联段评
+    fn export_chapter(
+        &self,
+        chapter: &Chapter,
+        barrages: &Barrages,
+        exported: &mut ExportedText,
+    ) -> Result<()> {
+        let chapter_id = chapter.id;
+        let stored = match self.library {
+            Some(library) => library.content_of(chapter_id)?,
+            None => None,
+        };
+        let (content, rows) = match stored {
+            Some(content) if self.annotations == 0 => (content, None),
+            stored => {
+                let resp = self.proxy.chapter_content(chapter_id)?;
+                let content = resp
+                    .expand
+                    .and_then(|e| e.content)
+                    .filter(|e| !e.is_empty())
+                    .or(stored)
+                    .unwrap_or_default();
+                (content, Some(resp.rowNum))
+            }
+        };
+        let paragraphs: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .collect();
+
+        let text = &mut exported.text;
+        writeln!(text, "{}\n", chapter.title)?;
+        if paragraphs.is_empty() {
+            exported.locked_chapters.push(chapter_id);
+            return Ok(());
+        }
+
+        let annotations = if self.annotations == 0 {
+            Annotations::default()
+        } else if rows != Some(paragraphs.len()) {
+            exported.unaligned_chapters.push(chapter_id);
+            Annotations::default()
+        } else {
+            self.annotations_of(chapter_id, paragraphs.len())?
+        };
+
+        let mut footnotes = vec![];
+        for (index, paragraph) in paragraphs.iter().enumerate() {
+            text.push_str(paragraph);
+            for annotation in annotations.top(chapter_id, index + 1, self.annotations) {
+                footnotes.push(format!("{}: {}", annotation.nickname, annotation.content));
+                write!(text, "[{}]", footnotes.len())?;
+            }
+            text.push('\n');
+        }
+        if !footnotes.is_empty() {
+            text.push('\n');
+            for (index, footnote) in footnotes.iter().enumerate() {
+                writeln!(text, "[{}] {footnote}", index + 1)?;
+            }
+        }
+        let barrages = barrages.of(chapter_id);
+        if !barrages.is_empty() {
+            text.push('\n');
+            for barrage in barrages {
+                writeln!(text, "弹幕 {}: {}", barrage.nickname, barrage.content)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 导出整本小说的文本，未解锁的章节只保留标题
+    pub fn export(&self, novel_id: Id) -> Result<ExportedText> {
+        let novel = self.proxy.novel_info(novel_id)?;
+        let volumes = self.proxy.catalogue_of(novel_id)?.parse()?;
+
+        let barrages = match self.barrages {
+            true => self.barrages_of(novel_id)?,
+            false => Barrages::default(),
+        };
+
+        let mut exported = ExportedText::default();
+        writeln!(exported.text, "{}\n{}\n", novel.novelName, novel.authorName)?;
+        for volume in volumes {
+            writeln!(exported.text, "{}\n", volume.title)?;
+            for chapter in volume.chapters {
+                self.export_chapter(&chapter, &barrages, &mut exported)?;
+                exported.text.push('\n');
+            }
+        }
+        Ok(exported)
+    }
+}
@@ -0,0 +1,313 @@
+//! 小说更新的 Atom / RSS 订阅源
+
+use crate::{Id, Proxy, Timestamp, SERVER_UTC_OFFSET};
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+
+const WEBPREFIX: &str = "https://book.sfacg.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+/// 订阅源中的一个条目，对应一个章节
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedEntry {
+    pub novel_id: Id,             //<! 小说 ID
+    pub novel_name: String,       //<! 书名
+    pub author: String,           //<! 作者
+    pub chapter_id: Id,           //<! 章节 ID
+    pub title: String,            //<! 章节标题
+    pub volume: String,           //<! 所在卷名
+    pub total_chars: usize,       //<! 章节字数
+    pub creation_time: Timestamp, //<! 发布时间
+    pub is_vip: bool,             //<! 是否是 VIP 章节
+    pub link: String,             //<! 章节网页链接
+    pub excerpt: Option<String>,  //<! 章节内容摘录
+}
+
+pub struct FeedGenerator<'a> {
+    proxy: &'a Proxy,
+    title: String,  //<! 订阅源标题
+    limit: usize,   //<! 条目数量上限
+    excerpt: usize, //<! 摘录的字数
+}
+
+impl<'a> FeedGenerator<'a> {
+    pub fn new(proxy: &'a Proxy) -> Self {
+        Self {
+            proxy,
+            title: "SF 轻小说更新".to_string(),
+            limit: 50,
+            excerpt: 0,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// 只保留最新的 `n` 个章节
+    pub fn with_limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    /// 为每个条目摘录章节开头的 `n` 个字，无法获取内容的章节不摘录
+    pub fn with_excerpt(mut self, n: usize) -> Self {
+        self.excerpt = n;
+        self
+    }
+
+    fn excerpt_of(&self, chapter_id: Id) -> Option<String> {
+        let content = self
+            .proxy
+            .chapter_content(chapter_id)
+            .ok()?
+            .expand?
+            .content?;
+        let text: String = content
+            .lines()
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!text.is_empty()).then(|| text.chars().take(self.excerpt).collect())
+    }
+
+    /// 获取小说的章节，按发布时间从新到旧排列，获取失败的小说输出警告后跳过
+    pub fn entries(&self, novels: &[Id]) -> Result<Vec<FeedEntry>> {
+        let mut entries = vec![];
+        for novel_id in novels {
+            let fetched = self.proxy.novel_info(*novel_id).and_then(|novel| {
+                let volumes = self.proxy.catalogue_of(*novel_id)?.parse()?;
+                Ok((novel, volumes))
+            });
+            let (novel, volumes) = match fetched {
+                Ok(fetched) => fetched,
+                Err(err) => {
+                    eprintln!("warning: skipped novel {novel_id} in the feed: {err}");
+                    continue;
+                }
+            };
+            for volume in volumes {
+                for chapter in volume.chapters {
+                    entries.push(FeedEntry {
+                        novel_id: *novel_id,
+                        novel_name: novel.novelName.to_owned(),
+                        author: novel.authorName.to_owned(),
+                        chapter_id: chapter.id,
+                        title: chapter.title,
+                        volume: volume.title.to_owned(),
+                        total_chars: chapter.total_chars,
+                        creation_time: chapter.creation_time,
+                        is_vip: !chapter.is_free,
+                        link: format!(
+                            "{WEBPREFIX}/Novel/{novel_id}/{}/{}/",
+                            chapter.volume_id, chapter.id
+                        ),
+                        excerpt: None,
+                    });
+                }
+            }
+        }
+        entries.sort_by_key(|e| (-e.creation_time, -e.chapter_id));
+        entries.truncate(self.limit);
+
+        if self.excerpt > 0 {
+            for entry in entries.iter_mut() {
+                entry.excerpt = self.excerpt_of(entry.chapter_id);
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn generate(&self, format: FeedFormat, novels: &[Id]) -> Result<String> {
+        let entries = self.entries(novels)?;
+        match format {
+            FeedFormat::Atom => self.atom(novels, &entries),
+            FeedFormat::Rss => self.rss(&entries),
+        }
+    }
+
+    /// 生成 Atom 订阅源
+    pub fn atom(&self, novels: &[Id], entries: &[FeedEntry]) -> Result<String> {
+        let ids: Vec<String> = novels.iter().map(Id::to_string).collect();
+        let updated = entries.first().map_or(server_now(), |e| e.creation_time);
+
+        let mut xml = String::new();
+        writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(xml, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#)?;
+        writeln!(xml, "  <id>urn:sfutils:novels:{}</id>", ids.join(","))?;
+        writeln!(xml, "  <title>{}</title>", escape(&self.title))?;
+        writeln!(xml, "  <updated>{}</updated>", rfc3339(updated))?;
+        writeln!(xml, "  <generator>sfutils</generator>")?;
+        for entry in entries {
+            writeln!(xml, "  <entry>")?;
+            writeln!(xml, "    <id>{}</id>", escape(&entry.link))?;
+            writeln!(xml, "    <title>{}</title>", escape(&entry_title(entry)))?;
+            writeln!(xml, r#"    <link href="{}"/>"#, escape(&entry.link))?;
+            writeln!(
+                xml,
+                "    <updated>{}</updated>",
+                rfc3339(entry.creation_time)
+            )?;
+            writeln!(
+                xml,
+                "    <published>{}</published>",
+                rfc3339(entry.creation_time)
+            )?;
+            writeln!(
+                xml,
+                "    <author><name>{}</name></author>",
+                escape(&entry.author)
+            )?;
+            writeln!(xml, r#"    <category term="{}"/>"#, escape(&entry.volume))?;
+            if entry.is_vip {
+                writeln!(xml, r#"    <category term="VIP"/>"#)?;
+            }
+            writeln!(xml, "    <summary>{}</summary>", escape(&summary(entry)))?;
+            if let Some(excerpt) = &entry.excerpt {
+                writeln!(
+                    xml,
+                    r#"    <content type="text">{}</content>"#,
+                    escape(excerpt)
+                )?;
+            }
+            writeln!(xml, "  </entry>")?;
+        }
+        writeln!(xml, "</feed>")?;
+        Ok(xml)
+    }
+
+    /// 生成 RSS 2.0 订阅源
+    pub fn rss(&self, entries: &[FeedEntry]) -> Result<String> {
+        let mut xml = String::new();
+        writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(
+            xml,
+            r#"<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">"#
+        )?;
+        writeln!(xml, "  <channel>")?;
+        writeln!(xml, "    <title>{}</title>", escape(&self.title))?;
+        writeln!(xml, "    <link>{WEBPREFIX}/</link>")?;
+        writeln!(
+            xml,
+            "    <description>{}</description>",
+            escape(&self.title)
+        )?;
+        writeln!(xml, "    <generator>sfutils</generator>")?;
+        if let Some(entry) = entries.first() {
+            writeln!(
+                xml,
+                "    <lastBuildDate>{}</lastBuildDate>",
+                rfc2822(entry.creation_time)
+            )?;
+        }
+        for entry in entries {
+            let description = match &entry.excerpt {
+                Some(excerpt) => format!("{}\n\n{excerpt}", summary(entry)),
+                None => summary(entry),
+            };
+            writeln!(xml, "    <item>")?;
+            writeln!(xml, "      <title>{}</title>", escape(&entry_title(entry)))?;
+            writeln!(xml, "      <link>{}</link>", escape(&entry.link))?;
+            writeln!(xml, "      <guid>{}</guid>", escape(&entry.link))?;
+            writeln!(
+                xml,
+                "      <dc:creator>{}</dc:creator>",
+                escape(&entry.author)
+            )?;
+            writeln!(xml, "      <category>{}</category>", escape(&entry.volume))?;
+            if entry.is_vip {
+                writeln!(xml, "      <category>VIP</category>")?;
+            }
+            writeln!(
+                xml,
+                "      <pubDate>{}</pubDate>",
+                rfc2822(entry.creation_time)
+            )?;
+            writeln!(
+                xml,
+                "      <description>{}</description>",
+                escape(&description)
+            )?;
+            writeln!(xml, "    </item>")?;
+        }
+        writeln!(xml, "  </channel>")?;
+        writeln!(xml, "</rss>")?;
+        Ok(xml)
+    }
+}
+
+fn entry_title(entry: &FeedEntry) -> String {
+    format!("{} {}", entry.novel_name, entry.title)
+}
+
+fn summary(entry: &FeedEntry) -> String {
+    format!(
+        "{} · {} 字{}",
+        entry.volume,
+        entry.total_chars,
+        if entry.is_vip { " · VIP" } else { "" }
+    )
+}
+
+/// 当前时间，与服务端时间戳一样将北京时间按 UTC 计
+pub(crate) fn server_now() -> Timestamp {
+    Utc::now().timestamp() + SERVER_UTC_OFFSET
+}
+
+/// 将按 UTC 解析的服务端时间戳还原为带 +08:00 偏移的北京时间
+fn server_time(timestamp: Timestamp) -> DateTime<FixedOffset> {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET as i32).unwrap();
+    offset
+        .timestamp_opt(timestamp - SERVER_UTC_OFFSET, 0)
+        .single()
+        .unwrap_or_else(|| offset.timestamp_opt(0, 0).unwrap())
+}
+
+pub(crate) fn rfc3339(timestamp: Timestamp) -> String {
+    server_time(timestamp).to_rfc3339()
+}
+
+fn rfc2822(timestamp: Timestamp) -> String {
+    server_time(timestamp).to_rfc2822()
+}
+
+/// 转义 XML 文本中的特殊字符
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 不允许除换行与制表符以外的控制字符
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_timestamp;
+
+    #[test]
+    fn times_are_in_server_time_zone() {
+        let timestamp = to_timestamp("2023-05-20T08:00:00").unwrap();
+        assert_eq!(rfc3339(timestamp), "2023-05-20T08:00:00+08:00");
+        assert_eq!(rfc2822(timestamp), "Sat, 20 May 2023 08:00:00 +0800");
+    }
+}
@@ -1 +1,33 @@
 //! 有声小说信息
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Track {
+    pub album_id: Id,             //<! 有声小说 ID
+    pub id: Id,                   //<! 音频 ID
+    pub title: String,            //<! 标题
+    pub order: usize,             //<! 音频序号
+    pub duration: Option<usize>,  //<! 时长（秒）
+    pub is_free: bool,            //<! 是否是免费音频
+    pub price: usize,             //<! 现价
+    pub creation_time: Timestamp, //<! 创建时间
+    pub url: Option<String>,      //<! 音频地址，仅在获取音频详情且已解锁时存在
+}
+
+#[derive(Debug, Serialize)]
+pub struct Album {
+    pub id: Id,                      //<! 有声小说 ID
+    pub novel_id: Id,                //<! 原作小说 ID
+    pub name: String,                //<! 专辑名
+    pub author: Option<String>,      //<! 作者，收藏夹中的有声小说不含作者名
+    pub author_id: Id,               //<! 作者 ID
+    pub tags: Vec<String>,           //<! 标签（系统标签在前）
+    pub intro: Option<String>,       //<! 简介，收藏夹中的有声小说不含简介
+    pub cover: String,               //<! 封面 URL
+    pub total_views: usize,          //<! 访问次数
+    pub is_finished: Option<bool>,   //<! 是否完结，收藏夹与衍生作品中的有声小说不含完结状态
+    pub last_update_time: Timestamp, //<! 最后一次更新时间
+    pub tracks: Vec<Track>,          //<! 音频列表，收藏夹中的有声小说不含音频
+}
@@ -0,0 +1,79 @@
+//! 段评与弹幕信息
+
+use super::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+pub struct Annotation {
+    pub id: Id,                   //<! 段评 ID
+    pub chapter_id: Id,           //<! 章节 ID
+    pub row: usize,               //<! 段落所在的行号
+    pub user_id: Id,              //<! 评论者 ID
+    pub nickname: String,         //<! 评论者昵称
+    pub content: String,          //<! 段评内容
+    pub total_likes: usize,       //<! 点赞数
+    pub creation_time: Timestamp, //<! 评论时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct Barrage {
+    pub id: Id,                   //<! 弹幕 ID
+    pub novel_id: Id,             //<! 小说 ID
+    pub chapter_id: Option<Id>,   //<! 章节 ID
+    pub user_id: Id,              //<! 发送者 ID
+    pub nickname: String,         //<! 发送者昵称
+    pub content: String,          //<! 弹幕内容
+    pub creation_time: Timestamp, //<! 发送时间
+}
+
+#[derive(Debug, Default)]
+pub struct Annotations {
+    pub items: BTreeMap<(Id, usize), Vec<Annotation>>, //<! 以 (章节 ID, 行号) 索引的段评
+}
+
+impl Annotations {
+    pub fn insert(&mut self, annotation: Annotation) {
+        self.items
+            .entry((annotation.chapter_id, annotation.row))
+            .or_default()
+            .push(annotation);
+    }
+
+    /// 获取段落点赞数最高的前 `n` 条段评
+    pub fn top(&self, chapter_id: Id, row: usize, n: usize) -> Vec<&Annotation> {
+        let mut items: Vec<&Annotation> = self
+            .items
+            .get(&(chapter_id, row))
+            .map(|e| e.iter().collect())
+            .unwrap_or_default();
+        items.sort_by_key(|e| std::cmp::Reverse(e.total_likes));
+        items.truncate(n);
+        items
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Barrages {
+    pub items: BTreeMap<Option<Id>, Vec<Barrage>>, //<! 以章节 ID 索引的弹幕，不属于任何章节的弹幕以 None 索引
+}
+
+impl Barrages {
+    pub fn insert(&mut self, barrage: Barrage) {
+        self.items
+            .entry(barrage.chapter_id)
+            .or_default()
+            .push(barrage);
+    }
+
+    /// 获取章节的弹幕，按发送时间排列
+    pub fn of(&self, chapter_id: Id) -> Vec<&Barrage> {
+        let mut items: Vec<&Barrage> = self
+            .items
+            .get(&Some(chapter_id))
+            .map(|e| e.iter().collect())
+            .unwrap_or_default();
+        items.sort_by_key(|e| e.creation_time);
+        items
+    }
+}
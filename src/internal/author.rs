@@ -1 +1,69 @@
 //! 作者信息
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Announcement {
+    pub id: Id,                   //<! 公告 ID
+    pub title: String,            //<! 标题
+    pub content: String,          //<! 内容
+    pub creation_time: Timestamp, //<! 发布时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorStats {
+    pub total_novels: usize,                 //<! 作品数
+    pub finished_novels: usize,              //<! 已完结的作品数
+    pub total_chars: usize,                  //<! 总字数
+    pub total_views: usize,                  //<! 总访问次数
+    pub total_likes: usize,                  //<! 总点赞量
+    pub total_favorites: usize,              //<! 总收藏数
+    pub total_tickets: usize,                //<! 当月总票数
+    pub last_update_time: Option<Timestamp>, //<! 最近一次更新时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct Author {
+    pub profile: User,                    //<! 用户信息
+    pub pen_name: Option<String>,         //<! 笔名，仅作者本人可见
+    pub novels: Vec<Novel>,               //<! 作品列表
+    pub announcements: Vec<Announcement>, //<! 作者公告，仅作者本人可见
+    pub stats: AuthorStats,               //<! 作品的汇总统计
+}
+
+impl AuthorStats {
+    pub fn new(novels: &[Novel]) -> Self {
+        Self {
+            total_novels: novels.len(),
+            finished_novels: novels.iter().filter(|e| e.is_finished).count(),
+            total_chars: novels.iter().map(|e| e.total_chars).sum(),
+            total_views: novels.iter().map(|e| e.total_views).sum(),
+            total_likes: novels.iter().map(|e| e.total_likes).sum(),
+            total_favorites: novels.iter().map(|e| e.total_favorites).sum(),
+            total_tickets: novels.iter().map(|e| e.total_tickets).sum(),
+            last_update_time: novels.iter().map(|e| e.last_update_time).max(),
+        }
+    }
+}
+
+impl Author {
+    /// 汇总作者的作品统计，作品按最后更新时间从新到旧排列
+    pub fn new(profile: User, mut novels: Vec<Novel>) -> Self {
+        novels.sort_by_key(|e| -e.last_update_time);
+        Self {
+            profile,
+            pen_name: None,
+            stats: AuthorStats::new(&novels),
+            novels,
+            announcements: vec![],
+        }
+    }
+
+    /// 附加作者本人可见的笔名与公告
+    pub fn with_private(mut self, pen_name: String, announcements: Vec<Announcement>) -> Self {
+        self.pen_name = Some(pen_name);
+        self.announcements = announcements;
+        self
+    }
+}
@@ -1 +1,32 @@
 //! 漫画信息
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ComicChapter {
+    pub comic_id: Id,             //<! 漫画 ID
+    pub id: Id,                   //<! 章节 ID
+    pub title: String,            //<! 标题
+    pub order: usize,             //<! 章节序号
+    pub is_free: bool,            //<! 是否是免费章节
+    pub price: usize,             //<! 现价
+    pub creation_time: Timestamp, //<! 创建时间
+    pub update_time: Timestamp,   //<! 最后更新时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct Comic {
+    pub id: Id,                      //<! 漫画 ID
+    pub name: String,                //<! 漫画名
+    pub author: Option<String>,      //<! 作者，收藏夹中的漫画不含作者名
+    pub author_id: Id,               //<! 作者 ID
+    pub tags: Vec<String>,           //<! 标签（系统标签在前）
+    pub intro: Option<String>,       //<! 简介，收藏夹中的漫画不含简介
+    pub cover: String,               //<! 封面 URL
+    pub banner: String,              //<! 背景横幅 URL
+    pub total_views: usize,          //<! 访问次数
+    pub is_finished: bool,           //<! 是否完结
+    pub last_update_time: Timestamp, //<! 最后一次更新时间
+    pub chapters: Vec<ComicChapter>, //<! 章节列表，收藏夹中的漫画不含章节
+}
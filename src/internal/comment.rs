@@ -0,0 +1,55 @@
+//! 评论信息
+
+use super::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize)]
+pub struct Reply {
+    pub id: Id,                   //<! 回复 ID
+    pub comment_id: Id,           //<! 所属评论 ID
+    pub user_id: Id,              //<! 回复者 ID
+    pub nickname: String,         //<! 回复者昵称
+    pub content: String,          //<! 回复内容
+    pub reply_to: Option<Id>,     //<! 被回复的用户 ID
+    pub creation_time: Timestamp, //<! 回复时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct Comment {
+    pub id: Id,                   //<! 评论 ID
+    pub novel_id: Id,             //<! 小说 ID
+    pub user_id: Id,              //<! 评论者 ID
+    pub nickname: String,         //<! 评论者昵称
+    pub title: Option<String>,    //<! 标题（仅长评）
+    pub content: String,          //<! 评论内容
+    pub is_long: bool,            //<! 是否是长评
+    pub is_sticky: bool,          //<! 是否置顶
+    pub total_likes: usize,       //<! 点赞数
+    pub total_replies: usize,     //<! 回复数
+    pub creation_time: Timestamp, //<! 评论时间
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thread {
+    pub comment: Comment,    //<! 评论
+    pub replies: Vec<Reply>, //<! 按时间排序的回复
+}
+
+impl Thread {
+    /// 将回复按所属评论归并到对应的评论下
+    pub fn stitch(comments: Vec<Comment>, replies: Vec<Reply>) -> Vec<Thread> {
+        let mut groups: HashMap<Id, Vec<Reply>> = HashMap::new();
+        for reply in replies {
+            groups.entry(reply.comment_id).or_default().push(reply);
+        }
+        comments
+            .into_iter()
+            .map(|comment| {
+                let mut replies = groups.remove(&comment.id).unwrap_or_default();
+                replies.sort_by_key(|e| e.creation_time);
+                Thread { comment, replies }
+            })
+            .collect()
+    }
+}
@@ -1,8 +1,9 @@
 //! 收藏夹信息
 
 use super::*;
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Favoirtes {
     pub id: Id,                   //<! 收藏夹 ID
     pub owner_id: Id,             //<! 所有者 ID（用户 ID）
@@ -11,5 +12,7 @@ pub struct Favoirtes {
     pub creation_time: Timestamp, //<! 创建时间
     pub modifiable: bool,         //<! 是否可修改（对于操作者而言）
     pub is_full: bool,            //<! 容量是否达到上限
-    pub refs: Vec<Id>,            //<! 收藏夹内容索引
+    pub refs: Vec<Id>,            //<! 收藏夹内容索引（小说、漫画或有声小说的 ID）
+    pub comics: Vec<Comic>, //<! 漫画收藏夹中的漫画，与 `refs` 一一对应，直接转换时不含作者名、简介与章节
+    pub albums: Vec<Album>, //<! 有声小说收藏夹中的有声小说，与 `refs` 一一对应，不含作者名、简介与音频
 }
@@ -0,0 +1,147 @@
+//! 消费账本
+
+use super::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+pub struct Consumption {
+    pub novel_id: Id,               //<! 消费目标所在项的 ID（小说 ID）
+    pub novel_name: Option<String>, //<! 消费目标所在项的名称
+    pub chapter_id: Option<Id>,     //<! 章节 ID
+    pub title: Option<String>,      //<! 消费项标题
+    pub r#type: String,             //<! 消费类型
+    pub fire_coins: usize,          //<! 消费的火卷
+    pub vouchers: usize,            //<! 消费的代金卷
+    pub time: Timestamp,            //<! 消费时间（UNIX 时间戳）
+}
+
+#[derive(Debug, Serialize)]
+pub struct LedgerEntry {
+    pub novel_id: Id,                     //<! 消费目标所在项的 ID
+    pub r#type: String,                   //<! 消费类型
+    pub novel_name: Option<String>,       //<! 书名
+    pub total_items: usize,               //<! 消费次数
+    pub total_fire_coins: usize,          //<! 累计消费的火卷
+    pub total_vouchers: usize,            //<! 累计消费的代金卷
+    pub first_time: Timestamp,            //<! 首次消费时间
+    pub last_time: Timestamp,             //<! 最近一次消费时间
+    pub monthly: BTreeMap<String, usize>, //<! 按月统计的火卷消费（YYYY-MM）
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>, //<! 按消费类型与所在项汇总的消费项，以累计火卷降序排列
+}
+
+impl Ledger {
+    /// 按消费类型与所在项汇总消费记录，忽略 `since` 之前的消费
+    ///
+    /// 不同类型的消费项（如小说与漫画）可能有相同的 ID，故以二者共同作为键
+    pub fn aggregate(records: Vec<Consumption>, since: Option<Timestamp>) -> Self {
+        let mut entries: BTreeMap<(String, Id), LedgerEntry> = BTreeMap::new();
+        for record in records {
+            if since.is_some_and(|since| record.time < since) {
+                continue;
+            }
+            let key = (record.r#type.clone(), record.novel_id);
+            let entry = entries.entry(key).or_insert_with(|| LedgerEntry {
+                novel_id: record.novel_id,
+                r#type: record.r#type.clone(),
+                novel_name: None,
+                total_items: 0,
+                total_fire_coins: 0,
+                total_vouchers: 0,
+                first_time: record.time,
+                last_time: record.time,
+                monthly: BTreeMap::new(),
+            });
+            if entry.novel_name.is_none() {
+                entry.novel_name = record.novel_name;
+            }
+            entry.total_items += 1;
+            entry.total_fire_coins += record.fire_coins;
+            entry.total_vouchers += record.vouchers;
+            entry.first_time = entry.first_time.min(record.time);
+            entry.last_time = entry.last_time.max(record.time);
+            *entry.monthly.entry(to_month(record.time)).or_default() += record.fire_coins;
+        }
+        let mut entries: Vec<LedgerEntry> = entries.into_values().collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.total_fire_coins));
+        Self { entries }
+    }
+
+    /// 累计消费的火卷
+    pub fn total_fire_coins(&self) -> usize {
+        self.entries.iter().map(|e| e.total_fire_coins).sum()
+    }
+
+    /// 累计消费的代金卷
+    pub fn total_vouchers(&self) -> usize {
+        self.entries.iter().map(|e| e.total_vouchers).sum()
+    }
+}
+
+/// 按服务端所在时区（北京时间）划分月份
+#[inline]
+fn to_month(timestamp: Timestamp) -> String {
+    chrono::NaiveDateTime::from_timestamp_opt(timestamp + SERVER_UTC_OFFSET, 0)
+        .map(|e| e.format("%Y-%m").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumption(r#type: &str, novel_id: Id, fire_coins: usize, time: Timestamp) -> Consumption {
+        Consumption {
+            novel_id,
+            novel_name: Some(format!("{type}-{novel_id}")),
+            chapter_id: None,
+            title: None,
+            r#type: r#type.to_string(),
+            fire_coins,
+            vouchers: 1,
+            time,
+        }
+    }
+
+    #[test]
+    fn aggregate_keys_by_type_and_id() {
+        let ledger = Ledger::aggregate(
+            vec![
+                consumption("chaps", 1, 10, 1_700_000_000),
+                consumption("chaps", 1, 20, 1_690_000_000),
+                consumption("comic", 1, 50, 1_700_000_000),
+            ],
+            None,
+        );
+        assert_eq!(ledger.entries.len(), 2);
+        assert_eq!(ledger.entries[0].r#type, "comic");
+        assert_eq!(ledger.entries[0].total_fire_coins, 50);
+        let novel = &ledger.entries[1];
+        assert_eq!(novel.r#type, "chaps");
+        assert_eq!(novel.total_items, 2);
+        assert_eq!(novel.total_fire_coins, 30);
+        assert_eq!(novel.total_vouchers, 2);
+        assert_eq!(novel.first_time, 1_690_000_000);
+        assert_eq!(novel.last_time, 1_700_000_000);
+        assert_eq!(novel.monthly.len(), 2);
+        assert_eq!(ledger.total_fire_coins(), 80);
+    }
+
+    #[test]
+    fn aggregate_skips_records_before_since() {
+        let ledger = Ledger::aggregate(
+            vec![
+                consumption("chaps", 1, 10, 100),
+                consumption("chaps", 1, 20, 200),
+            ],
+            Some(150),
+        );
+        assert_eq!(ledger.entries.len(), 1);
+        assert_eq!(ledger.entries[0].total_fire_coins, 20);
+        assert_eq!(ledger.entries[0].first_time, 200);
+    }
+}
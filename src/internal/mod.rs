@@ -1,16 +1,18 @@
 use anyhow::Result;
+use serde::Serialize;
 
 pub type Timestamp = i64;
 pub type Id = i32;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Type {
     Comic,
     Novel,
     Album,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum VerifyType {
     None(String),
 }
@@ -45,18 +47,59 @@ pub fn to_timestamp(date: &str) -> Result<Timestamp> {
         .timestamp())
 }
 
+/// 服务端时间所在时区（北京时间）与 UTC 的偏移秒数
+pub const SERVER_UTC_OFFSET: i64 = 8 * 3600;
+
+/// 将服务端给出的北京时间转换为 UNIX 时间戳
+///
+/// [`to_timestamp`] 将时间按 UTC 解析，得到的时间戳比实际晚 8 小时，
+/// 需要与本地时间比较或对外输出时使用此函数
+#[inline]
+pub fn to_unix_timestamp(date: &str) -> Result<Timestamp> {
+    Ok(to_timestamp(date)? - SERVER_UTC_OFFSET)
+}
+
 mod album;
+mod annotation;
 mod author;
 mod comic;
+mod comment;
 mod favorites;
+mod ledger;
 mod novel;
 mod private;
+mod progress;
+mod purchase;
+mod rank;
+mod search;
+mod stats;
+mod update;
 mod user;
 
 pub use album::*;
+pub use annotation::*;
 pub use author::*;
 pub use comic::*;
+pub use comment::*;
 pub use favorites::*;
+pub use ledger::*;
 pub use novel::*;
 pub use private::*;
+pub use progress::*;
+pub use purchase::*;
+pub use rank::*;
+pub use search::*;
+pub use stats::*;
+pub use update::*;
 pub use user::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_time_is_china_time() {
+        // 北京时间 2023-05-20 08:00 即 UTC 2023-05-20 00:00
+        assert_eq!(to_unix_timestamp("2023-05-20T08:00:00").unwrap(), 1684540800);
+    }
+}
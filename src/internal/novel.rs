@@ -1,8 +1,9 @@
 ////! 小说信息
 
 use super::*;
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Chapter {
     pub novel_id: Id,             //<! 小说 ID
     pub volume_id: Id,            //<! 卷 ID
@@ -13,11 +14,12 @@ pub struct Chapter {
     pub creation_time: Timestamp, //<! 创建时间
     pub update_time: Timestamp,   //<! 最后更新时间
     pub is_free: bool,            //<! 是否是免费章节
-    pub price: usize,             //<! 现价
+    pub price: usize,             //<! 当前账户解锁所需的火卷（已包含 VIP 折扣）
     pub origin_price: usize,      //<! 原价
     pub content: Option<String>,  //<! 章节内容
 }
 
+#[derive(Debug, Serialize)]
 pub struct Volume {
     pub novel_id: Id,           //<! 小说 ID
     pub id: Id,                 //<! 卷 ID
@@ -27,16 +29,15 @@ pub struct Volume {
 }
 
 /// TODO: 添加价格信息
-///
-/// TODO: 添加小说标签信息
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Novel {
     pub name: String,                //<! 书名
     pub id: Id,                      //<! 小说 ID
     pub author: String,              //<! 作者
     pub author_id: Id,               //<! 作者 ID
     pub r#type: String,              //<! 小说类型
+    pub tags: Vec<String>,           //<! 标签（系统标签在前）
     pub intro: String,               //<! 简介
     pub sign_status: String,         //<! 签约状态
     pub sign_level: String,          //<! 签约等级
@@ -52,3 +53,11 @@ pub struct Novel {
     pub last_update_time: Timestamp, //<! 最后一次更新时间
     pub creation_time: Timestamp,    //<! 创建时间
 }
+
+/// 同一作品的衍生作品
+#[derive(Debug, Serialize)]
+pub struct DerivativeWorks {
+    pub novel_id: Id,       //<! 原作小说 ID
+    pub comics: Vec<Comic>, //<! 漫画，不含作者名、简介与章节
+    pub albums: Vec<Album>, //<! 有声小说，不含作者名、简介与音频
+}
@@ -1,41 +1,56 @@
 //! 用户个人信息
 
 use super::*;
+use serde::Serialize;
 
-/// TODO: 增加是否允许更改昵称的信息
-///
-/// # 相关结构
-/// { changeNickNameInfo: { canChange: bool, nextChangeNeedDays: usize } }
-///
-/// # 改名规则
-/// 1. VIP 1 以上每年可以修改一次，每次扣除 750 余额
-/// 2. VIP 5 及以上免费，但仍受时间限制
-/// 3. 使用改名卡修改昵称不受时间限制
-///
-/// TODO: 增加余额信息（火卷）
-
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct VipInfo {
     pub point: usize,                  //<! 当前点数
     pub level: usize,                  //<! 当前等级
     pub next_level: usize,             //<! 下一个等级
     pub point_of_next_level: usize,    //<! 下一个等级所需的总点数
-    pub discount: usize,               //<! 当前等级的折扣
-    pub next_discount: usize,          //<! 下一个折扣点的折扣
+    pub discount: usize,               //<! 当前等级的折扣（减免的百分比，如 10 表示九折）
+    pub next_discount: usize,          //<! 下一个折扣点的折扣（含义同上）
     pub level_of_next_discount: usize, //<! 下一个折扣点所在的等级
     pub point_of_next_discount: usize, //<! 下一个折扣点所需的总点数
 }
 
-#[derive(Debug)]
+/// # 改名规则
+/// 1. VIP 1 以上每年可以修改一次，每次扣除 750 余额
+/// 2. VIP 5 及以上免费，但仍受时间限制
+/// 3. 使用改名卡修改昵称不受时间限制
+#[derive(Debug, Serialize)]
+pub struct NicknameChange {
+    pub can_change: bool,       //<! 当前是否允许修改昵称
+    pub days_until_next: usize, //<! 距离下一次允许修改的天数
+}
+
+#[derive(Debug, Serialize)]
 pub struct UserPrivate {
-    pub id: Id,                       //<! 用户 ID
-    pub area_code: u32,               //<! 手机区号
-    pub phone: String,                //<! 绑定的手机号码
-    pub email: String,                //<! 绑定的电子邮箱
-    pub is_author: bool,              //<! 是否是作者
-    pub vip: VipInfo,                 //<! VIP 信息
-    pub registration_time: Timestamp, //<! 注册时间
-    pub banlance: usize,              //<! 账户余额
-    pub vouchers: usize,              //<! 代金卷数量（用于替代余额）
-    pub tokens: usize,                //<! 代币数量（用于兑换福利物品）
+    pub id: Id,                                  //<! 用户 ID
+    pub nickname: String,                        //<! 昵称
+    pub area_code: u32,                          //<! 手机区号
+    pub phone: String,                           //<! 绑定的手机号码
+    pub email: String,                           //<! 绑定的电子邮箱
+    pub is_author: bool,                         //<! 是否是作者
+    pub vip: VipInfo,                            //<! VIP 信息
+    pub registration_time: Timestamp,            //<! 注册时间
+    pub banlance: usize,                         //<! 账户余额（火卷）
+    pub vouchers: usize,                         //<! 代金卷数量（用于替代余额）
+    pub tokens: usize,                           //<! 代币数量（用于兑换福利物品）
+    pub welfare_money: f32,                      //<! 福利金额
+    pub nickname_change: Option<NicknameChange>, //<! 昵称修改信息，服务端未返回时为空
+}
+
+impl NicknameChange {
+    /// 不使用改名卡时修改昵称所需的余额，无法修改时返回 None
+    pub fn cost(&self, vip_level: usize) -> Option<usize> {
+        if !self.can_change || vip_level < 1 {
+            None
+        } else if vip_level >= 5 {
+            Some(0)
+        } else {
+            Some(750)
+        }
+    }
 }
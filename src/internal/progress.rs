@@ -0,0 +1,22 @@
+//! 阅读进度
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadingProgress {
+    pub novel_id: Id,           //<! 小说 ID
+    pub chapter_id: Id,         //<! 当前阅读的章节 ID
+    pub row: usize,             //<! 当前阅读的段落行号
+    pub update_time: Timestamp, //<! 更新时间（UNIX 时间戳）
+}
+
+/// 阅读记录
+#[derive(Debug, Serialize)]
+pub struct NovelView {
+    pub novel_id: Id,                  //<! 小说 ID
+    pub novel_name: String,            //<! 书名
+    pub chapter_id: Id,                //<! 最后阅读的章节 ID
+    pub chapter_title: Option<String>, //<! 最后阅读的章节标题
+    pub view_time: String,             //<! 阅读时间
+}
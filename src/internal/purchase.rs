@@ -0,0 +1,106 @@
+//! 章节购买计划
+
+use super::*;
+use anyhow::bail;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct PurchaseItem {
+    pub chapter_id: Id,      //<! 章节 ID
+    pub title: String,       //<! 章节标题
+    pub origin_price: usize, //<! 原价
+    pub price: usize,        //<! 当前账户的实付价格
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurchasePlan {
+    pub novel_id: Id,             //<! 小说 ID
+    pub items: Vec<PurchaseItem>, //<! 待购买的章节
+}
+
+impl PurchasePlan {
+    /// 从章节列表中挑选出未解锁的章节
+    ///
+    /// 章节的 `price` 即服务端按当前账户给出的实付价格，已包含 VIP 折扣，故不再重复计算折扣
+    pub fn new<'a>(novel_id: Id, chapters: impl IntoIterator<Item = &'a Chapter>) -> Self {
+        let items = chapters
+            .into_iter()
+            .filter(|e| !e.is_free && e.price > 0)
+            .map(|e| PurchaseItem {
+                chapter_id: e.id,
+                title: e.title.to_owned(),
+                origin_price: e.origin_price,
+                price: e.price,
+            })
+            .collect();
+        Self { novel_id, items }
+    }
+
+    /// 总价
+    pub fn total(&self) -> usize {
+        self.items.iter().map(|e| e.price).sum()
+    }
+
+    /// 总原价
+    pub fn origin_total(&self) -> usize {
+        self.items.iter().map(|e| e.origin_price).sum()
+    }
+
+    /// 检查预算与余额是否足够完成购买
+    pub fn check(&self, budget: usize, balance: usize) -> anyhow::Result<()> {
+        let total = self.total();
+        if total > budget {
+            bail!("total cost {total} exceeds the budget {budget}");
+        }
+        if total > balance {
+            bail!("insufficient balance: {balance} available, {total} required");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(id: Id, is_free: bool, price: usize, origin_price: usize) -> Chapter {
+        Chapter {
+            novel_id: 1,
+            volume_id: 1,
+            id,
+            title: format!("Chapter {id}"),
+            order: id as usize,
+            total_chars: 1000,
+            creation_time: 0,
+            update_time: 0,
+            is_free,
+            price,
+            origin_price,
+            content: None,
+        }
+    }
+
+    #[test]
+    fn plan_uses_account_price() {
+        let chapters = vec![
+            chapter(1, true, 0, 0),
+            chapter(2, false, 0, 10),
+            chapter(3, false, 9, 10),
+            chapter(4, false, 18, 20),
+        ];
+        let plan = PurchasePlan::new(1, &chapters);
+        let ids: Vec<Id> = plan.items.iter().map(|e| e.chapter_id).collect();
+        assert_eq!(ids, vec![3, 4]);
+        assert_eq!(plan.total(), 27);
+        assert_eq!(plan.origin_total(), 30);
+    }
+
+    #[test]
+    fn check_budget_and_balance() {
+        let chapters = vec![chapter(1, false, 10, 10), chapter(2, false, 10, 10)];
+        let plan = PurchasePlan::new(1, &chapters);
+        assert!(plan.check(20, 20).is_ok());
+        assert!(plan.check(19, 100).is_err());
+        assert!(plan.check(100, 19).is_err());
+    }
+}
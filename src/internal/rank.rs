@@ -0,0 +1,19 @@
+//! 榜单信息
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct RankEntry {
+    pub rank: usize,            //<! 排名
+    pub user_id: Id,            //<! 用户 ID
+    pub nickname: String,       //<! 昵称
+    pub avatar: Option<String>, //<! 头像 URL
+    pub amount: usize,          //<! 月票数或打赏的火卷数
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserRank {
+    pub rank: Option<usize>, //<! 当前用户的排名，未上榜时为空
+    pub amount: usize,       //<! 当前用户的月票数或打赏的火卷数
+}
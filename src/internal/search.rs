@@ -0,0 +1,127 @@
+//! 搜索结果
+
+use super::*;
+use serde::Serialize;
+use std::cmp::Reverse;
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub r#type: Type,                   //<! 作品类型
+    pub id: Id,                         //<! 作品 ID
+    pub name: String,                   //<! 作品名
+    pub author: Option<String>,         //<! 作者（仅小说）
+    pub author_id: Id,                  //<! 作者 ID
+    pub total_chars: Option<usize>,     //<! 总字数（仅小说）
+    pub total_views: usize,             //<! 访问次数
+    pub total_favorites: Option<usize>, //<! 总收藏数（仅小说）
+    pub is_finished: Option<bool>,      //<! 是否已完结，有声小说的搜索结果不含完结状态
+    pub last_update_time: Timestamp,    //<! 最后更新时间
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchOrder {
+    Views,  //<! 按访问次数降序
+    Marks,  //<! 按收藏数降序
+    Update, //<! 按更新时间降序
+}
+
+/// 客户端的搜索结果过滤条件
+#[derive(Debug, Default)]
+pub struct SearchFilter {
+    pub r#type: Option<Type>, //<! 限定作品类型
+    pub finished: bool,       //<! 仅保留已完结的作品，不排除完结状态未知的作品
+    pub min_chars: usize,     //<! 最少字数，仅对小说有效，非零时排除其它作品
+}
+
+impl SearchFilter {
+    pub fn matches(&self, hit: &SearchHit) -> bool {
+        if self.r#type.is_some_and(|e| e != hit.r#type) {
+            return false;
+        }
+        if self.finished && hit.is_finished == Some(false) {
+            return false;
+        }
+        self.min_chars == 0 || hit.total_chars.is_some_and(|e| e >= self.min_chars)
+    }
+}
+
+impl SearchOrder {
+    pub fn sort(self, hits: &mut [SearchHit]) {
+        match self {
+            SearchOrder::Views => hits.sort_by_key(|e| Reverse(e.total_views)),
+            SearchOrder::Marks => hits.sort_by_key(|e| Reverse(e.total_favorites)),
+            SearchOrder::Update => hits.sort_by_key(|e| Reverse(e.last_update_time)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(
+        r#type: Type,
+        id: Id,
+        total_chars: Option<usize>,
+        is_finished: Option<bool>,
+    ) -> SearchHit {
+        SearchHit {
+            r#type,
+            id,
+            name: id.to_string(),
+            author: None,
+            author_id: 0,
+            total_chars,
+            total_views: id as usize * 10,
+            total_favorites: total_chars.map(|_| 100 - id as usize),
+            is_finished,
+            last_update_time: id as Timestamp,
+        }
+    }
+
+    #[test]
+    fn filter_by_type_state_and_chars() {
+        let novel = hit(Type::Novel, 1, Some(5000), Some(true));
+        let comic = hit(Type::Comic, 2, None, Some(true));
+        let ongoing = hit(Type::Novel, 3, Some(50000), Some(false));
+        let album = hit(Type::Album, 4, None, None);
+
+        let filter = SearchFilter::default();
+        assert!([&novel, &comic, &ongoing].iter().all(|e| filter.matches(e)));
+
+        let filter = SearchFilter {
+            r#type: Some(Type::Novel),
+            ..Default::default()
+        };
+        assert!(filter.matches(&novel) && !filter.matches(&comic));
+
+        let filter = SearchFilter {
+            finished: true,
+            ..Default::default()
+        };
+        assert!(filter.matches(&comic) && !filter.matches(&ongoing));
+        // 完结状态未知的有声小说不被排除
+        assert!(filter.matches(&album));
+
+        let filter = SearchFilter {
+            min_chars: 10000,
+            ..Default::default()
+        };
+        assert!(filter.matches(&ongoing) && !filter.matches(&novel) && !filter.matches(&comic));
+    }
+
+    #[test]
+    fn sort_in_descending_order() {
+        let mut hits = vec![
+            hit(Type::Novel, 2, Some(1), Some(true)),
+            hit(Type::Novel, 3, Some(1), Some(true)),
+            hit(Type::Novel, 1, Some(1), Some(true)),
+        ];
+        SearchOrder::Views.sort(&mut hits);
+        assert_eq!(hits.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3, 2, 1]);
+        SearchOrder::Marks.sort(&mut hits);
+        assert_eq!(hits.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+        SearchOrder::Update.sort(&mut hits);
+        assert_eq!(hits.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+}
@@ -0,0 +1,77 @@
+//! 小说统计
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct VolumeStats {
+    pub id: Id,                //<! 卷 ID
+    pub title: String,         //<! 卷名
+    pub total_chapters: usize, //<! 章节数
+    pub total_chars: usize,    //<! 总字数
+}
+
+#[derive(Debug, Serialize)]
+pub struct NovelStats {
+    pub novel_id: Id,                          //<! 小说 ID
+    pub volumes: Vec<VolumeStats>,             //<! 各卷的统计
+    pub first_chapter_time: Option<Timestamp>, //<! 首个章节的发布时间
+    pub last_chapter_time: Option<Timestamp>,  //<! 最新章节的发布时间
+    pub average_interval: Option<f64>,         //<! 平均更新间隔（天）
+    pub recent_chapters: usize,                //<! 最近 30 天内发布的章节数
+    pub locked_chapters: usize,                //<! 未解锁的章节数
+    pub locked_cost: usize,                    //<! 解锁全部章节所需的火卷
+}
+
+impl NovelStats {
+    /// 根据目录统计字数、更新频率和未解锁章节的总价，总价按获取目录的账户计算
+    ///
+    /// `now` 为 UNIX 时间戳，章节的发布时间与 [`to_timestamp`] 一致，比较前换算为 UNIX 时间戳
+    pub fn new(novel_id: Id, volumes: &[Volume], now: Timestamp) -> Self {
+        let chapters: Vec<&Chapter> = volumes.iter().flat_map(|e| &e.chapters).collect();
+
+        let mut times: Vec<Timestamp> = chapters.iter().map(|e| e.creation_time).collect();
+        times.sort_unstable();
+        let average_interval = match (times.first(), times.last()) {
+            (Some(first), Some(last)) if times.len() > 1 => {
+                Some((last - first) as f64 / (times.len() - 1) as f64 / 86400.0)
+            }
+            _ => None,
+        };
+        let recent_chapters = times
+            .iter()
+            .filter(|e| now - (**e - SERVER_UTC_OFFSET) < 30 * 86400)
+            .count();
+
+        let plan = PurchasePlan::new(novel_id, chapters);
+
+        Self {
+            novel_id,
+            volumes: volumes
+                .iter()
+                .map(|e| VolumeStats {
+                    id: e.id,
+                    title: e.title.to_owned(),
+                    total_chapters: e.chapters.len(),
+                    total_chars: e.chapters.iter().map(|e| e.total_chars).sum(),
+                })
+                .collect(),
+            first_chapter_time: times.first().copied(),
+            last_chapter_time: times.last().copied(),
+            average_interval,
+            recent_chapters,
+            locked_chapters: plan.items.len(),
+            locked_cost: plan.total(),
+        }
+    }
+
+    /// 总字数
+    pub fn total_chars(&self) -> usize {
+        self.volumes.iter().map(|e| e.total_chars).sum()
+    }
+
+    /// 总章节数
+    pub fn total_chapters(&self) -> usize {
+        self.volumes.iter().map(|e| e.total_chapters).sum()
+    }
+}
@@ -0,0 +1,25 @@
+//! 书架更新
+
+use super::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewChapter {
+    pub id: Id,                   //<! 章节 ID
+    pub title: String,            //<! 标题
+    pub volume: String,           //<! 所在卷名
+    pub is_free: bool,            //<! 是否是免费章节
+    pub creation_time: Timestamp, //<! 发布时间
+}
+
+/// 书架中小说的一次更新
+#[derive(Debug, Clone, Serialize)]
+pub struct NovelUpdate {
+    pub novel_id: Id,                  //<! 小说 ID
+    pub novel_name: String,            //<! 书名
+    pub author: String,                //<! 作者
+    pub last_update_time: Timestamp,   //<! 最后一次更新时间
+    pub total_chars: usize,            //<! 总字数
+    pub added_chars: isize,            //<! 相比上次快照增加的字数
+    pub new_chapters: Vec<NewChapter>, //<! 新增的章节
+}
@@ -1,8 +1,9 @@
 //! 用户信息
 
 use super::*;
+use serde::Serialize;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct User {
     pub id: Id,                  //<! 用户 ID
     pub uuid: String,            //<! 用户唯一标识码
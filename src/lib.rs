@@ -1,7 +1,15 @@
+pub mod audio;
 mod boluobao;
 pub mod bridge;
 pub mod cli;
+pub mod download;
+pub mod ebook;
+pub mod export;
+pub mod feed;
 mod internal;
+pub mod local;
+pub mod notify;
+pub mod opds;
 pub mod top;
 
 pub use boluobao::*;
@@ -0,0 +1,94 @@
+use super::{data_local_dir, ChapterCache, Library};
+use crate::{Id, Timestamp};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalChapter {
+    pub id: Id,        //<! 章节 ID
+    pub title: String, //<! 标题
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalVolume {
+    pub title: String,               //<! 卷名
+    pub chapters: Vec<LocalChapter>, //<! 章节列表
+}
+
+/// 已下载小说的元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalBook {
+    pub id: Id,                      //<! 小说 ID
+    pub name: String,                //<! 书名
+    pub author: String,              //<! 作者
+    pub author_id: Id,               //<! 作者 ID
+    pub tags: Vec<String>,           //<! 标签
+    pub intro: String,               //<! 简介
+    pub is_finished: bool,           //<! 是否完结
+    pub last_update_time: Timestamp, //<! 最后一次更新时间
+    pub cover: String,               //<! 封面 URL
+    pub volumes: Vec<LocalVolume>,   //<! 目录
+    pub fetch_time: Timestamp,       //<! 下载时间
+}
+
+/// 下载目录，小说的元数据、目录与章节内容存储在书库 `library.db` 中，
+/// 封面存储在 `<小说 ID>/cover.jpg`
+///
+/// 旧版本将章节内容存储为 `<小说 ID>/<章节 ID>.txt`（元数据为 `<小说 ID>/book.json`），
+/// 这些章节在下载时导入书库，`book.json` 不再使用
+pub struct BookStore {
+    dir: PathBuf,
+}
+
+impl BookStore {
+    /// 打开默认的下载目录，即章节缓存所在的目录
+    pub fn open() -> Result<Self> {
+        Self::open_at(data_local_dir()?.join("cache"))
+    }
+
+    pub fn open_at(dir: PathBuf) -> Result<Self> {
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        Ok(Self { dir })
+    }
+
+    fn dir_of(&self, novel_id: Id) -> PathBuf {
+        self.dir.join(novel_id.to_string())
+    }
+
+    /// 打开下载目录中的书库
+    pub fn library(&self) -> Result<Library> {
+        Library::open_at(&self.dir.join("library.db"))
+    }
+
+    /// 小说目录下以 `<章节 ID>.txt` 存储的章节，即旧版本下载目录与阅读器缓存的格式
+    pub fn cache_of(&self, novel_id: Id) -> Result<ChapterCache> {
+        ChapterCache::open_at(self.dir_of(novel_id))
+    }
+
+    pub fn has_cover(&self, novel_id: Id) -> bool {
+        self.dir_of(novel_id).join("cover.jpg").exists()
+    }
+
+    pub fn cover_of(&self, novel_id: Id) -> Option<Vec<u8>> {
+        fs::read(self.dir_of(novel_id).join("cover.jpg")).ok()
+    }
+
+    pub fn remove_cover(&self, novel_id: Id) -> Result<()> {
+        let path = self.dir_of(novel_id).join("cover.jpg");
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    pub fn save_cover(&self, novel_id: Id, data: &[u8]) -> Result<()> {
+        let dir = self.dir_of(novel_id);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("cover.jpg"), data)?;
+        Ok(())
+    }
+}
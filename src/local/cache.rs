@@ -0,0 +1,43 @@
+use super::data_local_dir;
+use crate::Id;
+
+use anyhow::Result;
+use std::{fs, path::PathBuf};
+
+/// 本地章节内容缓存，每个章节存储为 `cache/<小说 ID>/<章节 ID>.txt`
+pub struct ChapterCache {
+    dir: PathBuf,
+}
+
+impl ChapterCache {
+    /// 打开小说的章节缓存
+    pub fn open(novel_id: Id) -> Result<Self> {
+        Self::open_at(data_local_dir()?.join("cache").join(novel_id.to_string()))
+    }
+
+    /// 打开指定目录下的章节缓存
+    pub fn open_at(dir: PathBuf) -> Result<Self> {
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        Ok(Self { dir })
+    }
+
+    fn path_of(&self, chapter_id: Id) -> PathBuf {
+        self.dir.join(format!("{chapter_id}.txt"))
+    }
+
+    pub fn get(&self, chapter_id: Id) -> Option<String> {
+        fs::read_to_string(self.path_of(chapter_id)).ok()
+    }
+
+    pub fn put(&self, chapter_id: Id, content: &str) -> Result<()> {
+        fs::write(self.path_of(chapter_id), content)?;
+        Ok(())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        fs::remove_dir_all(&self.dir)?;
+        Ok(())
+    }
+}
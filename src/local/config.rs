@@ -0,0 +1,323 @@
+use crate::{consts, Id};
+
+use anyhow::{bail, Result};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+/// 配置档，未设置的项使用默认值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>, //<! 默认账号（昵称）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>, //<! 客户端版本
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>, //<! 客户端渠道
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>, //<! HTTP 代理
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>, //<! 默认输出格式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_dir: Option<PathBuf>, //<! 下载目录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>, //<! 并发数
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskKind {
+    SignIn,   //<! 每日签到
+    Refresh,  //<! 刷新凭证
+    Poll,     //<! 检查书架更新
+    Download, //<! 增量下载章节
+}
+
+/// 守护进程的定时任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>, //<! 任务名，用于记录运行状态，默认为任务类型
+    pub kind: TaskKind, //<! 任务类型
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>, //<! 运行间隔，形如 `30m`、`6h`、`1d`，签到任务每天运行一次
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<String>, //<! 执行任务的账号，为空时使用全部账号
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub novels: Vec<Id>, //<! 下载的小说，为空时使用书架中的小说
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exec: Vec<String>, //<! 检查到更新时执行的命令，与 `watch --exec` 相同
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<String>, //<! 检查到更新时 POST 的 URL，与 `watch --webhook` 相同
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<PathBuf>, //<! 检查到的更新追加到的 JSONL 文件，与 `watch --log` 相同
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    #[serde(default)]
+    pub jitter: u64, //<! 任务到期后随机推迟的最大秒数
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<Task>,
+}
+
+/// 配置文件，以 TOML 的形式存储在配置目录的 `config.toml` 中
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+}
+
+impl Profile {
+    pub const KEYS: [&'static str; 7] = [
+        "account",
+        "app_version",
+        "channel",
+        "proxy",
+        "output",
+        "download_dir",
+        "concurrency",
+    ];
+
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(match key {
+            "account" => self.account.clone(),
+            "app_version" => self.app_version.clone(),
+            "channel" => self.channel.clone(),
+            "proxy" => self.proxy.clone(),
+            "output" => self.output.clone(),
+            "download_dir" => self
+                .download_dir
+                .as_ref()
+                .map(|e| e.to_string_lossy().to_string()),
+            "concurrency" => self.concurrency.map(|e| e.to_string()),
+            _ => bail!("unknown config key: {key}"),
+        })
+    }
+
+    /// 设置配置项，`value` 为 `None` 时移除该项
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<()> {
+        let value = value.map(str::to_string);
+        match key {
+            "account" => self.account = value,
+            "app_version" => {
+                if let Some(version) = &value {
+                    if !consts::APPKEYS.contains_key(version) {
+                        bail!("invalid app version: {version}");
+                    }
+                }
+                self.app_version = value;
+            }
+            "channel" => self.channel = value,
+            "proxy" => {
+                if let Some(url) = &value {
+                    check_proxy_url(url)?;
+                }
+                self.proxy = value;
+            }
+            "output" => {
+                if let Some(output) = &value {
+                    if !["table", "json", "yaml", "csv", "debug"].contains(&output.as_str()) {
+                        bail!("invalid output format: {output}");
+                    }
+                }
+                self.output = value;
+            }
+            "download_dir" => self.download_dir = value.map(PathBuf::from),
+            "concurrency" => {
+                self.concurrency = match value {
+                    Some(value) => match value.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => bail!("invalid concurrency: {value}"),
+                    },
+                    None => None,
+                }
+            }
+            _ => bail!("unknown config key: {key}"),
+        }
+        Ok(())
+    }
+
+    /// 应用形如 `SFUTILS_<KEY>` 的环境变量覆盖配置项
+    ///
+    /// 无效的值被忽略并输出警告，以免错误的环境变量导致包括 `config set` 在内的所有命令都无法运行
+    pub fn with_env(mut self) -> Self {
+        for key in Self::KEYS {
+            let name = format!("SFUTILS_{}", key.to_uppercase());
+            if let Ok(value) = env::var(&name) {
+                if let Err(err) = self.set(key, Some(&value)) {
+                    eprintln!("warning: ignored {name}: {err}");
+                }
+            }
+        }
+        self
+    }
+
+    /// 并发数，默认为 4
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.unwrap_or(4)
+    }
+}
+
+/// 检查 HTTP 代理的地址，需为带有主机名的 http、https 或 socks5 地址
+///
+/// `reqwest::Proxy::all` 会为缺少协议的地址补上 `http://`，单独检查以拒绝 `garbage` 之类的值
+fn check_proxy_url(url: &str) -> Result<()> {
+    let parsed =
+        reqwest::Url::parse(url).map_err(|err| anyhow::anyhow!("invalid proxy {url}: {err}"))?;
+    if !["http", "https", "socks5", "socks5h"].contains(&parsed.scheme())
+        || parsed.host_str().is_none()
+    {
+        bail!("invalid proxy: {url}");
+    }
+    reqwest::Proxy::all(url).map_err(|err| anyhow::anyhow!("invalid proxy {url}: {err}"))?;
+    Ok(())
+}
+
+impl Task {
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.to_owned(),
+            None => match self.kind {
+                TaskKind::SignIn => "sign-in",
+                TaskKind::Refresh => "refresh",
+                TaskKind::Poll => "poll",
+                TaskKind::Download => "download",
+            }
+            .to_string(),
+        }
+    }
+
+    /// 运行间隔（秒）
+    pub fn interval(&self) -> Result<i64> {
+        let interval = match (&self.interval, self.kind) {
+            (Some(interval), _) => interval.as_str(),
+            (None, TaskKind::Poll) => "1h",
+            (None, TaskKind::Download) => "6h",
+            (None, _) => "1d",
+        };
+        parse_interval(interval)
+    }
+}
+
+/// 解析形如 `30m`、`6h`、`1d` 的时间间隔，返回秒数
+pub fn parse_interval(interval: &str) -> Result<i64> {
+    let (value, unit) = interval.split_at(interval.len().saturating_sub(1));
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => bail!("invalid interval: {interval}"),
+    };
+    match value.parse::<i64>() {
+        Ok(value) if value > 0 => Ok(value * scale),
+        _ => bail!("invalid interval: {interval}"),
+    }
+}
+
+impl Config {
+    /// 配置文件的位置，可由环境变量 `SFUTILS_CONFIG` 指定
+    pub fn path() -> PathBuf {
+        match env::var_os("SFUTILS_CONFIG") {
+            Some(path) => PathBuf::from(path),
+            None => ProjectDirs::from("", "", "sfutils")
+                .unwrap()
+                .config_dir()
+                .join("config.toml"),
+        }
+    }
+
+    /// 打开默认位置的配置文件
+    pub fn open() -> Result<Self> {
+        Self::open_at(Self::path())
+    }
+
+    pub fn open_at(path: PathBuf) -> Result<Self> {
+        let mut config: Config = if path.exists() {
+            toml::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Config::default()
+        };
+        config.path = path;
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// 获取配置档，不存在时返回空的配置档
+    pub fn profile(&self, name: &str) -> Profile {
+        self.profiles.get(name).cloned().unwrap_or_default()
+    }
+
+    pub fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        self.profiles.entry(name.to_string()).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_profile_keys() {
+        let mut profile = Profile::default();
+        profile.set("concurrency", Some("8")).unwrap();
+        profile.set("output", Some("json")).unwrap();
+        assert_eq!(profile.concurrency(), 8);
+        assert_eq!(profile.get("output").unwrap().as_deref(), Some("json"));
+
+        assert!(profile.set("concurrency", Some("0")).is_err());
+        assert!(profile.set("output", Some("xml")).is_err());
+        assert!(profile.set("unknown", Some("1")).is_err());
+        assert_eq!(profile.concurrency(), 8);
+
+        profile.set("concurrency", None).unwrap();
+        assert_eq!(profile.concurrency(), 4);
+    }
+
+    #[test]
+    fn invalid_env_values_are_ignored() {
+        env::set_var("SFUTILS_CONCURRENCY", "many");
+        let profile = Profile::default().with_env();
+        env::remove_var("SFUTILS_CONCURRENCY");
+        assert_eq!(profile.concurrency, None);
+    }
+
+    #[test]
+    fn invalid_proxies_are_rejected() {
+        let mut profile = Profile::default();
+        profile.set("proxy", Some("http://127.0.0.1:8080")).unwrap();
+        for proxy in ["garbage", "not a url", "ftp://127.0.0.1", "http://"] {
+            assert!(profile.set("proxy", Some(proxy)).is_err(), "{proxy}");
+        }
+        assert_eq!(profile.proxy.as_deref(), Some("http://127.0.0.1:8080"));
+
+        env::set_var("SFUTILS_PROXY", "garbage");
+        let profile = Profile::default().with_env();
+        env::remove_var("SFUTILS_PROXY");
+        assert_eq!(profile.proxy, None);
+    }
+
+    #[test]
+    fn parse_intervals() {
+        assert_eq!(parse_interval("30s").unwrap(), 30);
+        assert_eq!(parse_interval("30m").unwrap(), 30 * 60);
+        assert_eq!(parse_interval("6h").unwrap(), 6 * 60 * 60);
+        assert_eq!(parse_interval("1d").unwrap(), 24 * 60 * 60);
+        for interval in ["", "d", "0h", "-1h", "1w", "1.5h"] {
+            assert!(parse_interval(interval).is_err(), "{interval}");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::data_local_dir;
+use crate::Timestamp;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io::Write, path::PathBuf};
+
+/// 任务最近一次运行的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRecord {
+    pub last_run: Timestamp, //<! 运行时间
+    pub ok: bool,            //<! 是否成功
+    pub message: String,     //<! 运行结果
+    #[serde(default)]
+    pub failures: u32, //<! 连续失败的次数，成功后清零
+}
+
+/// 守护进程的运行状态，以 JSON 的形式存储在 `daemon.json` 中
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DaemonState {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    pub pid: Option<u32>, //<! 最近一次启动的进程 ID
+    #[serde(default)]
+    pub started: Option<Timestamp>, //<! 最近一次启动的时间
+    #[serde(default)]
+    pub tasks: BTreeMap<String, TaskRecord>, //<! 各任务的运行记录
+}
+
+impl DaemonState {
+    /// 打开默认位置的运行状态
+    pub fn open() -> Result<Self> {
+        Self::open_at(data_local_dir()?.join("daemon.json"))
+    }
+
+    pub fn open_at(path: PathBuf) -> Result<Self> {
+        let mut state: DaemonState = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            DaemonState::default()
+        };
+        state.path = path;
+        Ok(state)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 记录任务的运行结果，失败时累计连续失败的次数
+    pub fn record(&mut self, name: &str, time: Timestamp, result: &Result<String>) {
+        let failures = self.tasks.get(name).map_or(0, |e| e.failures);
+        let (ok, message, failures) = match result {
+            Ok(message) => (true, message.to_owned(), 0),
+            Err(err) => (false, err.to_string(), failures + 1),
+        };
+        self.tasks.insert(
+            name.to_string(),
+            TaskRecord {
+                last_run: time,
+                ok,
+                message,
+                failures,
+            },
+        );
+    }
+}
+
+/// 追加一行守护进程日志到 `daemon.log`
+pub fn append_daemon_log(line: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_local_dir()?.join("daemon.log"))?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
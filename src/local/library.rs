@@ -0,0 +1,773 @@
+use super::{
+    snippet_of, term_frequencies, tokenize, LocalBook, LocalChapter, LocalVolume, SearchHit,
+};
+use crate::{Chapter, Id, Novel, Timestamp, Volume};
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use std::{collections::HashMap, path::Path};
+
+/// 数据库迁移，第 `i` 个迁移将 `user_version` 从 `i` 升级到 `i + 1`
+const MIGRATIONS: &[&str] = &[
+    "
+    CREATE TABLE novels (
+        id               INTEGER PRIMARY KEY,
+        name             TEXT    NOT NULL,
+        author           TEXT    NOT NULL,
+        author_id        INTEGER NOT NULL,
+        type             TEXT    NOT NULL,
+        intro            TEXT    NOT NULL,
+        sign_status      TEXT    NOT NULL,
+        sign_level       TEXT    NOT NULL,
+        total_chars      INTEGER NOT NULL,
+        total_chapters   INTEGER NOT NULL,
+        total_views      INTEGER NOT NULL,
+        total_likes      INTEGER NOT NULL,
+        total_favorites  INTEGER NOT NULL,
+        total_tickets    INTEGER NOT NULL,
+        cover            TEXT    NOT NULL,
+        banner           TEXT    NOT NULL,
+        is_finished      INTEGER NOT NULL,
+        last_update_time INTEGER NOT NULL,
+        creation_time    INTEGER NOT NULL,
+        fetch_time       INTEGER NOT NULL
+    );
+    CREATE TABLE tags (
+        novel_id INTEGER NOT NULL REFERENCES novels (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        tag      TEXT    NOT NULL,
+        PRIMARY KEY (novel_id, tag)
+    );
+    CREATE INDEX tags_tag ON tags (tag);
+    CREATE TABLE volumes (
+        id       INTEGER PRIMARY KEY,
+        novel_id INTEGER NOT NULL REFERENCES novels (id) ON DELETE CASCADE,
+        title    TEXT    NOT NULL,
+        ord      INTEGER NOT NULL
+    );
+    CREATE TABLE chapters (
+        id            INTEGER PRIMARY KEY,
+        novel_id      INTEGER NOT NULL REFERENCES novels (id) ON DELETE CASCADE,
+        volume_id     INTEGER NOT NULL,
+        title         TEXT    NOT NULL,
+        ord           INTEGER NOT NULL,
+        total_chars   INTEGER NOT NULL,
+        creation_time INTEGER NOT NULL,
+        update_time   INTEGER NOT NULL,
+        is_free       INTEGER NOT NULL,
+        price         INTEGER NOT NULL,
+        origin_price  INTEGER NOT NULL,
+        is_purchased  INTEGER NOT NULL,
+        content       TEXT,
+        fetch_time    INTEGER
+    );
+    CREATE INDEX chapters_novel ON chapters (novel_id);
+",
+    "
+    CREATE TABLE postings (
+        term       TEXT    NOT NULL,
+        chapter_id INTEGER NOT NULL REFERENCES chapters (id) ON DELETE CASCADE,
+        frequency  INTEGER NOT NULL,
+        PRIMARY KEY (term, chapter_id)
+    ) WITHOUT ROWID;
+    CREATE INDEX postings_chapter ON postings (chapter_id);
+    CREATE TABLE indexed (
+        chapter_id INTEGER PRIMARY KEY REFERENCES chapters (id) ON DELETE CASCADE,
+        length     INTEGER NOT NULL
+    );
+",
+];
+
+/// BM25 的词频饱和参数
+const BM25_K1: f64 = 1.2;
+
+/// BM25 的长度归一化参数
+const BM25_B: f64 = 0.75;
+
+/// 按是否包含完整查询重新排序的候选数量，为结果数量的倍数
+const PHRASE_RERANK: usize = 5;
+
+/// 书库中小说的查询条件，未设置的条件不参与过滤
+#[derive(Debug, Clone, Default)]
+pub struct LibraryQuery {
+    pub author: Option<String>,   //<! 作者名包含的文本
+    pub tag: Option<String>,      //<! 标签
+    pub finished: Option<bool>,   //<! 是否完结
+    pub min_chars: Option<usize>, //<! 最少字数
+    pub max_chars: Option<usize>, //<! 最多字数
+}
+
+/// 书库中的小说概要
+#[derive(Debug, Serialize)]
+pub struct LibraryEntry {
+    pub novel_id: Id,                //<! 小说 ID
+    pub name: String,                //<! 书名
+    pub author: String,              //<! 作者
+    pub is_finished: bool,           //<! 是否完结
+    pub total_chars: usize,          //<! 总字数
+    pub total_chapters: usize,       //<! 目录中的章节数
+    pub stored_chapters: usize,      //<! 已保存内容的章节数
+    pub last_update_time: Timestamp, //<! 最后一次更新时间
+    pub fetch_time: Timestamp,       //<! 最后一次获取的时间
+}
+
+/// 书库中的章节
+#[derive(Debug, Serialize)]
+pub struct LibraryChapter {
+    pub id: Id,                        //<! 章节 ID
+    pub title: String,                 //<! 标题
+    pub total_chars: usize,            //<! 总字数
+    pub is_free: bool,                 //<! 是否是免费章节
+    pub is_purchased: bool,            //<! 是否已购买
+    pub fetch_time: Option<Timestamp>, //<! 保存内容的时间，未保存时为 `None`
+}
+
+#[derive(Debug, Serialize)]
+pub struct LibraryVolume {
+    pub id: Id,                        //<! 卷 ID
+    pub title: String,                 //<! 卷名
+    pub chapters: Vec<LibraryChapter>, //<! 章节列表
+}
+
+/// 重建章节的索引
+fn index_chapter(conn: &Connection, chapter_id: Id, content: &str) -> Result<()> {
+    let (frequencies, length) = term_frequencies(content);
+    conn.execute("DELETE FROM postings WHERE chapter_id = ?1", [chapter_id])?;
+    let mut stmt = conn.prepare_cached("INSERT INTO postings VALUES (?1, ?2, ?3)")?;
+    for (term, frequency) in frequencies {
+        stmt.execute(params![term, chapter_id, frequency])?;
+    }
+    conn.execute(
+        "INSERT OR REPLACE INTO indexed VALUES (?1, ?2)",
+        params![chapter_id, length],
+    )?;
+    Ok(())
+}
+
+/// 以 SQLite 存储的本地书库，保存小说的元数据、目录与章节内容
+pub struct Library {
+    conn: Connection,
+}
+
+impl Library {
+    pub fn open_at(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let mut library = Self { conn };
+        library.migrate()?;
+        Ok(library)
+    }
+
+    /// 依次执行尚未应用的迁移
+    fn migrate(&mut self) -> Result<()> {
+        let version: usize = self
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", index + 1)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    /// 写入小说的元数据与目录，保留已保存的章节内容，移除目录中已不存在的章节
+    pub fn upsert(
+        &mut self,
+        novel: &Novel,
+        volumes: &[Volume],
+        fetch_time: Timestamp,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO novels VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
+                ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20
+            ) ON CONFLICT (id) DO UPDATE SET
+                name = ?2, author = ?3, author_id = ?4, type = ?5, intro = ?6,
+                sign_status = ?7, sign_level = ?8, total_chars = ?9, total_chapters = ?10,
+                total_views = ?11, total_likes = ?12, total_favorites = ?13, total_tickets = ?14,
+                cover = ?15, banner = ?16, is_finished = ?17, last_update_time = ?18,
+                creation_time = ?19, fetch_time = ?20",
+            params![
+                novel.id,
+                novel.name,
+                novel.author,
+                novel.author_id,
+                novel.r#type,
+                novel.intro,
+                novel.sign_status,
+                novel.sign_level,
+                novel.total_chars,
+                novel.total_chapters,
+                novel.total_views,
+                novel.total_likes,
+                novel.total_favorites,
+                novel.total_tickets,
+                novel.cover,
+                novel.banner,
+                novel.is_finished,
+                novel.last_update_time,
+                novel.creation_time,
+                fetch_time,
+            ],
+        )?;
+
+        tx.execute("DELETE FROM tags WHERE novel_id = ?1", [novel.id])?;
+        for (position, tag) in novel.tags.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO tags VALUES (?1, ?2, ?3)",
+                params![novel.id, position, tag],
+            )?;
+        }
+
+        let chapters: Vec<&Chapter> = volumes.iter().flat_map(|e| &e.chapters).collect();
+        tx.execute("DELETE FROM volumes WHERE novel_id = ?1", [novel.id])?;
+        for volume in volumes {
+            tx.execute(
+                "INSERT INTO volumes VALUES (?1, ?2, ?3, ?4)",
+                params![volume.id, novel.id, volume.title, volume.order],
+            )?;
+        }
+        let ids: Vec<String> = chapters.iter().map(|e| e.id.to_string()).collect();
+        tx.execute(
+            &format!(
+                "DELETE FROM chapters WHERE novel_id = ?1 AND id NOT IN ({})",
+                ids.join(",")
+            ),
+            [novel.id],
+        )?;
+        for chapter in chapters {
+            tx.execute(
+                "INSERT INTO chapters VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, NULL, NULL
+                ) ON CONFLICT (id) DO UPDATE SET
+                    volume_id = ?3, title = ?4, ord = ?5, total_chars = ?6,
+                    creation_time = ?7, update_time = ?8, is_free = ?9, price = ?10,
+                    origin_price = ?11, is_purchased = ?12",
+                params![
+                    chapter.id,
+                    chapter.novel_id,
+                    chapter.volume_id,
+                    chapter.title,
+                    chapter.order,
+                    chapter.total_chars,
+                    chapter.creation_time,
+                    chapter.update_time,
+                    chapter.is_free,
+                    chapter.price,
+                    chapter.origin_price,
+                    // 推测值：服务端只给出当前账号解锁所需的价格，已购买的 VIP 章节价格为 0，
+                    // 但限免等情况也会使价格为 0，且结果取决于下载时登录的账号
+                    !chapter.is_free && chapter.price == 0,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 保存章节内容并更新全文索引，返回章节是否在书库中
+    pub fn put_content(
+        &self,
+        chapter_id: Id,
+        content: &str,
+        fetch_time: Timestamp,
+    ) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let n = tx.execute(
+            "UPDATE chapters SET content = ?2, fetch_time = ?3 WHERE id = ?1",
+            params![chapter_id, content, fetch_time],
+        )?;
+        if n > 0 {
+            index_chapter(&tx, chapter_id, content)?;
+        }
+        tx.commit()?;
+        Ok(n > 0)
+    }
+
+    /// 为已保存内容但尚未索引的章节建立索引，返回新索引的章节数
+    pub fn index_pending(&self) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, content FROM chapters
+            WHERE content IS NOT NULL AND id NOT IN (SELECT chapter_id FROM indexed)",
+        )?;
+        let pending: Vec<(Id, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let tx = self.conn.unchecked_transaction()?;
+        for (chapter_id, content) in &pending {
+            index_chapter(&tx, *chapter_id, content)?;
+        }
+        tx.commit()?;
+        Ok(pending.len())
+    }
+
+    /// 章节的索引词频，单字的查询词以前缀匹配
+    fn postings_of(&self, term: &str, novel_id: Option<Id>) -> Result<HashMap<Id, usize>> {
+        // 索引词不含 U+0000，以 `term + U+0000` 为上界即精确匹配
+        let single = term.chars().count() == 1 && !term.is_ascii();
+        let upper = format!("{term}{}", if single { '\u{10FFFF}' } else { '\0' });
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT chapter_id, SUM(frequency) FROM postings
+            JOIN chapters ON chapters.id = postings.chapter_id
+            WHERE term >= ?1 AND term < ?2 AND (?3 IS NULL OR chapters.novel_id = ?3)
+            GROUP BY chapter_id",
+        )?;
+        let postings = stmt
+            .query_map(params![term, upper, novel_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(postings)
+    }
+
+    /// 在已保存的章节内容中搜索，结果须包含查询的所有索引词，按 BM25 得分从高到低排列
+    pub fn search(
+        &self,
+        query: &str,
+        novel_id: Option<Id>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        self.index_pending()?;
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut postings = vec![];
+        for term in &terms {
+            postings.push(self.postings_of(term, novel_id)?);
+        }
+        // 章节总数与平均长度与 `df` 一样只统计限定的小说
+        let (total, average): (usize, Option<f64>) = self.conn.query_row(
+            "SELECT COUNT(*), AVG(length) FROM indexed
+            JOIN chapters ON chapters.id = indexed.chapter_id
+            WHERE ?1 IS NULL OR chapters.novel_id = ?1",
+            [novel_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let average = average.unwrap_or(1.0).max(1.0);
+
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT length FROM indexed WHERE chapter_id = ?1")?;
+        let mut scores = vec![];
+        for chapter_id in postings[0].keys() {
+            if !postings.iter().all(|e| e.contains_key(chapter_id)) {
+                continue;
+            }
+            let length: usize = stmt.query_row([chapter_id], |row| row.get(0))?;
+            let norm = 1.0 - BM25_B + BM25_B * length as f64 / average;
+            let score: f64 = postings
+                .iter()
+                .map(|e| {
+                    let df = e.len() as f64;
+                    let idf = ((total as f64 - df + 0.5) / (df + 0.5) + 1.0).ln();
+                    let tf = e[chapter_id] as f64;
+                    idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * norm)
+                })
+                .sum();
+            scores.push((*chapter_id, score));
+        }
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        // 索引词相同不代表包含完整的查询，对得分靠前的章节按是否包含完整查询重新排序
+        scores.truncate(limit.saturating_mul(PHRASE_RERANK));
+
+        let phrase = query.trim().to_lowercase();
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT chapters.novel_id, novels.name, chapters.volume_id, volumes.title,
+                chapters.title, chapters.content
+            FROM chapters
+            JOIN novels ON novels.id = chapters.novel_id
+            LEFT JOIN volumes ON volumes.id = chapters.volume_id
+            WHERE chapters.id = ?1",
+        )?;
+        let mut hits = vec![];
+        for (chapter_id, score) in scores {
+            let hit = stmt.query_row([chapter_id], |row| {
+                let content: String = row.get(5)?;
+                let matched = content.to_lowercase().contains(&phrase);
+                let hit = SearchHit {
+                    novel_id: row.get(0)?,
+                    novel_name: row.get(1)?,
+                    volume_id: row.get(2)?,
+                    volume: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    chapter_id,
+                    chapter: row.get(4)?,
+                    score,
+                    snippet: snippet_of(&content, query, &terms),
+                };
+                Ok((matched, hit))
+            })?;
+            hits.push(hit);
+        }
+        // 排序是稳定的，同组内保持得分顺序
+        hits.sort_by_key(|(matched, _)| !matched);
+        Ok(hits.into_iter().take(limit).map(|(_, e)| e).collect())
+    }
+
+    pub fn content_of(&self, chapter_id: Id) -> Result<Option<String>> {
+        let content = self
+            .conn
+            .query_row(
+                "SELECT content FROM chapters WHERE id = ?1",
+                [chapter_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(content.flatten())
+    }
+
+    /// 尚未保存内容的章节
+    pub fn missing_chapters(&self, novel_id: Id) -> Result<Vec<Id>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT chapters.id FROM chapters JOIN volumes ON volumes.id = chapters.volume_id
+            WHERE chapters.novel_id = ?1 AND content IS NULL
+            ORDER BY volumes.ord, chapters.ord",
+        )?;
+        let ids = stmt
+            .query_map([novel_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(ids)
+    }
+
+    fn tags_of(&self, novel_id: Id) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tag FROM tags WHERE novel_id = ?1 ORDER BY position")?;
+        let tags = stmt
+            .query_map([novel_id], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(tags)
+    }
+
+    pub fn novel(&self, novel_id: Id) -> Result<Option<Novel>> {
+        let novel = self
+            .conn
+            .query_row("SELECT * FROM novels WHERE id = ?1", [novel_id], |row| {
+                Ok(Novel {
+                    id: row.get("id")?,
+                    name: row.get("name")?,
+                    author: row.get("author")?,
+                    author_id: row.get("author_id")?,
+                    r#type: row.get("type")?,
+                    tags: vec![],
+                    intro: row.get("intro")?,
+                    sign_status: row.get("sign_status")?,
+                    sign_level: row.get("sign_level")?,
+                    total_chars: row.get("total_chars")?,
+                    total_chapters: row.get("total_chapters")?,
+                    total_views: row.get("total_views")?,
+                    total_likes: row.get("total_likes")?,
+                    total_favorites: row.get("total_favorites")?,
+                    total_tickets: row.get("total_tickets")?,
+                    cover: row.get("cover")?,
+                    banner: row.get("banner")?,
+                    is_finished: row.get("is_finished")?,
+                    last_update_time: row.get("last_update_time")?,
+                    creation_time: row.get("creation_time")?,
+                })
+            })
+            .optional()?;
+        let Some(mut novel) = novel else {
+            return Ok(None);
+        };
+        novel.tags = self.tags_of(novel_id)?;
+        Ok(Some(novel))
+    }
+
+    /// 小说的目录与各章节的保存状态
+    pub fn volumes_of(&self, novel_id: Id) -> Result<Vec<LibraryVolume>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, title FROM volumes WHERE novel_id = ?1 ORDER BY ord")?;
+        let mut volumes: Vec<LibraryVolume> = stmt
+            .query_map([novel_id], |row| {
+                Ok(LibraryVolume {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    chapters: vec![],
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, total_chars, is_free, is_purchased, fetch_time FROM chapters
+            WHERE volume_id = ?1 ORDER BY ord",
+        )?;
+        for volume in volumes.iter_mut() {
+            volume.chapters = stmt
+                .query_map([volume.id], |row| {
+                    Ok(LibraryChapter {
+                        id: row.get(0)?,
+                        title: row.get(1)?,
+                        total_chars: row.get(2)?,
+                        is_free: row.get(3)?,
+                        is_purchased: row.get(4)?,
+                        fetch_time: row.get(5)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(volumes)
+    }
+
+    /// 查询小说，按最后更新时间从新到旧排列
+    pub fn query(&self, query: &LibraryQuery) -> Result<Vec<LibraryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT novels.id, name, author, is_finished, total_chars, last_update_time, fetch_time,
+                (SELECT COUNT(*) FROM chapters WHERE novel_id = novels.id),
+                (SELECT COUNT(*) FROM chapters WHERE novel_id = novels.id AND content IS NOT NULL)
+            FROM novels
+            WHERE (?1 IS NULL OR author LIKE '%' || ?1 || '%')
+                AND (?2 IS NULL OR EXISTS (SELECT 1 FROM tags WHERE novel_id = novels.id AND tag = ?2))
+                AND (?3 IS NULL OR is_finished = ?3)
+                AND (?4 IS NULL OR total_chars >= ?4)
+                AND (?5 IS NULL OR total_chars <= ?5)
+            ORDER BY last_update_time DESC",
+        )?;
+        let entries = stmt
+            .query_map(
+                params![
+                    query.author,
+                    query.tag,
+                    query.finished,
+                    query.min_chars,
+                    query.max_chars
+                ],
+                |row: &Row| {
+                    Ok(LibraryEntry {
+                        novel_id: row.get(0)?,
+                        name: row.get(1)?,
+                        author: row.get(2)?,
+                        is_finished: row.get(3)?,
+                        total_chars: row.get(4)?,
+                        last_update_time: row.get(5)?,
+                        fetch_time: row.get(6)?,
+                        total_chapters: row.get(7)?,
+                        stored_chapters: row.get(8)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(entries)
+    }
+
+    /// 以导出用的形式获取小说，目录中只包含已保存内容的章节
+    pub fn book(&self, novel_id: Id) -> Result<Option<LocalBook>> {
+        let Some(novel) = self.novel(novel_id)? else {
+            return Ok(None);
+        };
+        let fetch_time: Timestamp = self.conn.query_row(
+            "SELECT fetch_time FROM novels WHERE id = ?1",
+            [novel_id],
+            |row| row.get(0),
+        )?;
+        let volumes = self
+            .volumes_of(novel_id)?
+            .into_iter()
+            .map(|volume| LocalVolume {
+                title: volume.title,
+                chapters: volume
+                    .chapters
+                    .into_iter()
+                    .filter(|e| e.fetch_time.is_some())
+                    .map(|e| LocalChapter {
+                        id: e.id,
+                        title: e.title,
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(Some(LocalBook {
+            id: novel.id,
+            name: novel.name,
+            author: novel.author,
+            author_id: novel.author_id,
+            tags: novel.tags,
+            intro: novel.intro,
+            is_finished: novel.is_finished,
+            last_update_time: novel.last_update_time,
+            cover: novel.cover,
+            volumes,
+            fetch_time,
+        }))
+    }
+
+    /// 所有小说，按最后更新时间从新到旧排列
+    pub fn books(&self) -> Result<Vec<LocalBook>> {
+        let mut books = vec![];
+        for entry in self.query(&LibraryQuery::default())? {
+            books.extend(self.book(entry.novel_id)?);
+        }
+        Ok(books)
+    }
+
+    /// 移除小说及其章节，返回小说是否存在
+    pub fn remove(&self, novel_id: Id) -> Result<bool> {
+        let n = self
+            .conn
+            .execute("DELETE FROM novels WHERE id = ?1", [novel_id])?;
+        Ok(n > 0)
+    }
+
+    /// 整理数据库文件，回收已删除数据占用的空间
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn novel(id: Id) -> Novel {
+        Novel {
+            name: format!("小说{id}"),
+            id,
+            author: "作者".to_string(),
+            author_id: 1,
+            r#type: String::new(),
+            tags: vec!["标签".to_string()],
+            intro: String::new(),
+            sign_status: String::new(),
+            sign_level: String::new(),
+            total_chars: 0,
+            total_chapters: 0,
+            total_views: 0,
+            total_likes: 0,
+            total_favorites: 0,
+            total_tickets: 0,
+            cover: String::new(),
+            banner: String::new(),
+            is_finished: false,
+            last_update_time: 0,
+            creation_time: 0,
+        }
+    }
+
+    fn chapter(novel_id: Id, id: Id) -> Chapter {
+        Chapter {
+            novel_id,
+            volume_id: novel_id * 100,
+            id,
+            title: format!("第{id}章"),
+            order: id as usize,
+            total_chars: 0,
+            creation_time: 0,
+            update_time: 0,
+            is_free: true,
+            price: 0,
+            origin_price: 0,
+            content: None,
+        }
+    }
+
+    /// 将小说及其章节内容写入书库，章节 ID 为 `novel_id * 100 + 序号`
+    fn add(library: &mut Library, novel_id: Id, contents: &[&str]) {
+        let chapters: Vec<Chapter> = (1..=contents.len() as Id)
+            .map(|i| chapter(novel_id, novel_id * 100 + i))
+            .collect();
+        let volume = Volume {
+            novel_id,
+            id: novel_id * 100,
+            title: "第一卷".to_string(),
+            order: 1,
+            chapters,
+        };
+        library.upsert(&novel(novel_id), &[volume], 0).unwrap();
+        for (i, content) in contents.iter().enumerate() {
+            let chapter_id = novel_id * 100 + i as Id + 1;
+            assert!(library.put_content(chapter_id, content, 0).unwrap());
+        }
+    }
+
+    fn version(library: &Library) -> usize {
+        library
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn new_library_is_fully_migrated() {
+        let library = Library::open_at(Path::new(":memory:")).unwrap();
+        assert_eq!(version(&library), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn upgraded_library_indexes_stored_chapters() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        let mut library = Library { conn };
+        library.upsert(&novel(1), &[], 0).unwrap();
+        library
+            .conn
+            .execute(
+                "INSERT INTO chapters VALUES (101, 1, 100, '第一章', 1, 0, 0, 0, 1, 0, 0, 0, '魔法少女登场', 0)",
+                [],
+            )
+            .unwrap();
+
+        library.migrate().unwrap();
+        assert_eq!(version(&library), MIGRATIONS.len());
+        let hits = library.search("魔法", None, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].chapter_id, 101);
+        // 补建索引只进行一次
+        assert_eq!(library.index_pending().unwrap(), 0);
+    }
+
+    #[test]
+    fn search_requires_all_terms_and_ranks_phrases_first() {
+        let mut library = Library::open_at(Path::new(":memory:")).unwrap();
+        add(
+            &mut library,
+            1,
+            &["少女的魔法书", "魔法少女登场", "没有相关内容"],
+        );
+
+        let hits = library.search("魔法少女", None, 10).unwrap();
+        let ids: Vec<Id> = hits.iter().map(|e| e.chapter_id).collect();
+        assert_eq!(ids, [102]);
+
+        let hits = library.search("少女 魔法", None, 10).unwrap();
+        let ids: Vec<Id> = hits.iter().map(|e| e.chapter_id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(hits[0].snippet.contains("魔法"));
+
+        // 单字的查询以前缀匹配
+        assert_eq!(library.search("书", None, 10).unwrap().len(), 1);
+        assert!(library.search("，", None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn novel_filter_scopes_the_statistics() {
+        let mut scoped = Library::open_at(Path::new(":memory:")).unwrap();
+        add(&mut scoped, 1, &["魔法少女", "普通的一天"]);
+        add(
+            &mut scoped,
+            2,
+            &[
+                "魔法魔法魔法魔法魔法魔法",
+                "魔法",
+                "很长很长很长很长很长的内容",
+            ],
+        );
+        let mut alone = Library::open_at(Path::new(":memory:")).unwrap();
+        add(&mut alone, 1, &["魔法少女", "普通的一天"]);
+
+        let scoped = scoped.search("魔法", Some(1), 10).unwrap();
+        let alone = alone.search("魔法", None, 10).unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].chapter_id, alone[0].chapter_id);
+        assert!((scoped[0].score - alone[0].score).abs() < 1e-9);
+    }
+}
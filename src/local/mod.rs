@@ -0,0 +1,38 @@
+//! 本地数据存储
+
+mod books;
+mod cache;
+mod config;
+mod daemon;
+mod library;
+mod progress;
+mod search;
+mod snapshot;
+
+pub use books::*;
+pub use cache::*;
+pub use config::*;
+pub use daemon::*;
+pub use library::*;
+pub use progress::*;
+pub use search::*;
+pub use snapshot::*;
+
+use anyhow::Result;
+use directories::ProjectDirs;
+use std::{env, fs, path::PathBuf};
+
+/// 获取本地数据存储目录，不存在时自动创建，可由环境变量 `SFUTILS_DATA_DIR` 指定
+pub fn data_local_dir() -> Result<PathBuf> {
+    let dir = match env::var_os("SFUTILS_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => ProjectDirs::from("", "", "sfutils")
+            .unwrap()
+            .data_local_dir()
+            .to_owned(),
+    };
+    if !dir.exists() {
+        fs::create_dir_all(&dir)?;
+    }
+    Ok(dir)
+}
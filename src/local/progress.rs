@@ -0,0 +1,124 @@
+use super::data_local_dir;
+use crate::{Id, Proxy, ReadingProgress, Timestamp};
+
+use anyhow::Result;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// 本地阅读进度，以 JSON 的形式存储在 `progress.json` 中
+pub struct ProgressStore {
+    path: PathBuf,
+    items: BTreeMap<Id, ReadingProgress>,
+}
+
+impl ProgressStore {
+    /// 打开默认位置的阅读进度
+    pub fn open() -> Result<Self> {
+        Self::open_at(data_local_dir()?.join("progress.json"))
+    }
+
+    pub fn open_at(path: PathBuf) -> Result<Self> {
+        let items = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, items })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, serde_json::to_string_pretty(&self.items)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, novel_id: Id) -> Option<&ReadingProgress> {
+        self.items.get(&novel_id)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = &ReadingProgress> {
+        self.items.values()
+    }
+
+    pub fn remove(&mut self, novel_id: Id) -> Option<ReadingProgress> {
+        self.items.remove(&novel_id)
+    }
+
+    /// 更新阅读进度，只保留较新的一份，返回是否发生了更新
+    pub fn update(&mut self, progress: ReadingProgress) -> bool {
+        match self.items.get(&progress.novel_id) {
+            Some(e) if e.update_time >= progress.update_time => false,
+            _ => {
+                self.items.insert(progress.novel_id, progress);
+                true
+            }
+        }
+    }
+
+    /// 记录当前的阅读位置
+    pub fn mark(&mut self, novel_id: Id, chapter_id: Id, row: usize) {
+        let update_time = Proxy::timestamp().as_secs() as Timestamp;
+        self.items.insert(
+            novel_id,
+            ReadingProgress {
+                novel_id,
+                chapter_id,
+                row,
+                update_time,
+            },
+        );
+    }
+
+    /// 从服务端拉取小说的书签，服务端较新时覆盖本地进度
+    pub fn pull(&mut self, proxy: &Proxy, novel_id: Id) -> Result<bool> {
+        match proxy.bookmark_of(novel_id)? {
+            Some(bookmark) => Ok(self.update(bookmark.parse()?)),
+            None => Ok(false),
+        }
+    }
+
+    /// 将本地进度推送到服务端的书签，服务端的书签不比本地旧时不推送
+    ///
+    /// 只有服务端没有书签时直接推送，获取书签失败时返回错误，不覆盖服务端的书签
+    pub fn push(&self, proxy: &Proxy, novel_id: Id) -> Result<bool> {
+        let Some(local) = self.get(novel_id) else {
+            return Ok(false);
+        };
+        if let Some(remote) = proxy.bookmark_of(novel_id)? {
+            if remote.parse()?.update_time >= local.update_time {
+                return Ok(false);
+            }
+        }
+        proxy.set_bookmark(local.novel_id, local.chapter_id, local.row)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::mpsc, thread, time::Duration};
+
+    #[test]
+    fn push_fails_without_overwriting_when_bookmark_is_unavailable() {
+        // 以拒绝所有请求的 HTTP 代理模拟网络错误，并记录收到的请求
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                sender.send(request.method().to_string()).unwrap();
+                let _ = request.respond(tiny_http::Response::empty(502));
+            }
+        });
+
+        let path =
+            std::env::temp_dir().join(format!("sfutils-progress-{}.json", std::process::id()));
+        let mut store = ProgressStore::open_at(path).unwrap();
+        store.mark(1, 1001, 3);
+        let proxy = Proxy::builder().with_http_proxy(&url).build().unwrap();
+
+        assert!(store.push(&proxy, 1).is_err());
+        // 获取书签失败后不再发送更新书签的请求
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::Id;
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 摘要中匹配位置之前保留的字数
+const SNIPPET_BEFORE: usize = 30;
+
+/// 摘要中匹配位置之后保留的字数
+const SNIPPET_AFTER: usize = 60;
+
+/// 全文搜索的结果
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub novel_id: Id,       //<! 小说 ID
+    pub novel_name: String, //<! 书名
+    pub volume_id: Id,      //<! 卷 ID
+    pub volume: String,     //<! 卷名
+    pub chapter_id: Id,     //<! 章节 ID
+    pub chapter: String,    //<! 章节标题
+    pub score: f64,         //<! 相关度得分
+    pub snippet: String,    //<! 匹配位置附近的内容摘要
+}
+
+fn is_cjk(c: char) -> bool {
+    c.is_alphanumeric() && !c.is_ascii()
+}
+
+/// 切分文本为索引词，ASCII 字母与数字按单词切分并转为小写，其余文字按字切分为二元组，
+/// 每段连续文字的最后一个字单独成词，使单字的查询也能以前缀匹配
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    let mut run: Vec<char> = vec![];
+
+    let flush_run = |run: &mut Vec<char>, tokens: &mut Vec<String>| {
+        for i in 0..run.len() {
+            tokens.push(run[i..(i + 2).min(run.len())].iter().collect());
+        }
+        run.clear();
+    };
+    let flush_word = |word: &mut String, tokens: &mut Vec<String>| {
+        if !word.is_empty() {
+            tokens.push(std::mem::take(word));
+        }
+    };
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            flush_run(&mut run, &mut tokens);
+            word.push(c.to_ascii_lowercase());
+        } else if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            run.extend(c.to_lowercase());
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_run(&mut run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_run(&mut run, &mut tokens);
+    tokens
+}
+
+/// 统计各索引词的出现次数
+pub fn term_frequencies(text: &str) -> (HashMap<String, usize>, usize) {
+    let tokens = tokenize(text);
+    let total = tokens.len();
+    let mut frequencies = HashMap::new();
+    for token in tokens {
+        *frequencies.entry(token).or_default() += 1;
+    }
+    (frequencies, total)
+}
+
+/// 截取内容中查询词附近的摘要，优先定位完整的查询，其次定位第一个出现的索引词
+pub fn snippet_of(content: &str, query: &str, terms: &[String]) -> String {
+    let text: Vec<char> = content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .collect();
+    let lower: Vec<char> = text.iter().flat_map(|c| c.to_lowercase()).collect();
+    // 小写转换可能改变字数，此时无法对应位置，从头截取
+    let find = |needle: &str| {
+        let needle: Vec<char> = needle.to_lowercase().chars().collect();
+        if needle.is_empty() || lower.len() != text.len() {
+            return None;
+        }
+        lower.windows(needle.len()).position(|e| e == needle)
+    };
+    let position = find(query.trim())
+        .or_else(|| terms.iter().find_map(|e| find(e)))
+        .unwrap_or(0);
+
+    let start = position.saturating_sub(SNIPPET_BEFORE);
+    let end = (position + SNIPPET_AFTER).min(text.len());
+    let mut snippet: String = text[start..end].iter().collect();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_splits_cjk_into_bigrams_and_ascii_into_words() {
+        assert_eq!(
+            tokenize("魔法少女 Magic2 girl"),
+            ["魔法", "法少", "少女", "女", "magic2", "girl"]
+        );
+        assert_eq!(tokenize("你好，世界"), ["你好", "好", "世界", "界"]);
+        assert!(tokenize("  ，。!").is_empty());
+    }
+
+    #[test]
+    fn term_frequencies_count_tokens() {
+        let (frequencies, total) = term_frequencies("少女少女");
+        assert_eq!(total, 4);
+        assert_eq!(frequencies["少女"], 2);
+        assert_eq!(frequencies["女少"], 1);
+        assert_eq!(frequencies["女"], 1);
+    }
+
+    #[test]
+    fn snippet_prefers_the_whole_query() {
+        let content = format!("{}魔法少女登场{}", "前".repeat(40), "后".repeat(80));
+        let terms = tokenize("魔法少女");
+        let snippet = snippet_of(&content, "魔法少女", &terms);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("魔法少女登场"));
+        assert_eq!(snippet.chars().count(), SNIPPET_BEFORE + SNIPPET_AFTER + 2);
+
+        // 完整查询不存在时定位第一个出现的索引词
+        let snippet = snippet_of("短文中只有少女", "魔法少女", &terms);
+        assert_eq!(snippet, "短文中只有少女");
+    }
+}
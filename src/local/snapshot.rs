@@ -0,0 +1,61 @@
+use super::data_local_dir;
+use crate::{Id, Timestamp};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+};
+
+/// 书架中单本小说的快照
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NovelSnapshot {
+    pub name: String,                //<! 书名
+    pub last_update_time: Timestamp, //<! 最后一次更新时间
+    pub char_count: usize,           //<! 总字数
+    pub chapters: BTreeSet<Id>,      //<! 已知的章节 ID
+}
+
+/// 账号书架的快照，以 JSON 的形式存储在 `snapshots/<昵称>.json` 中
+pub struct ShelfSnapshot {
+    path: PathBuf,
+    novels: BTreeMap<Id, NovelSnapshot>,
+}
+
+impl ShelfSnapshot {
+    /// 打开账号在默认位置的快照
+    pub fn open(nickname: &str) -> Result<Self> {
+        let dir = data_local_dir()?.join("snapshots");
+        fs::create_dir_all(&dir)?;
+        Self::open_at(dir.join(format!("{nickname}.json")))
+    }
+
+    pub fn open_at(path: PathBuf) -> Result<Self> {
+        let novels = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, novels })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, serde_json::to_string_pretty(&self.novels)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, novel_id: Id) -> Option<&NovelSnapshot> {
+        self.novels.get(&novel_id)
+    }
+
+    pub fn insert(&mut self, novel_id: Id, snapshot: NovelSnapshot) {
+        self.novels.insert(novel_id, snapshot);
+    }
+
+    /// 只保留 `novels` 中的小说，移除已不在书架中的快照
+    pub fn retain(&mut self, novels: &BTreeSet<Id>) {
+        self.novels.retain(|id, _| novels.contains(id));
+    }
+}
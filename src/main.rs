@@ -4,6 +4,7 @@ use anyhow::Result;
 
 fn main() -> Result<()> {
     let matches = cli().get_matches();
+    init_profile(&matches)?;
 
     match matches.subcommand() {
         Some(("auth", matches)) => match matches.subcommand() {
@@ -13,6 +14,26 @@ fn main() -> Result<()> {
             Some(("refresh", matches)) => handle_auth_refresh(matches)?,
             _ => unreachable!(),
         },
+        Some(("spend", matches)) => handle_spend(matches)?,
+        Some(("buy", matches)) => handle_buy(matches)?,
+        Some(("comments", matches)) => handle_comments(matches)?,
+        Some(("export", matches)) => handle_export(matches)?,
+        Some(("rank", matches)) => handle_rank(matches)?,
+        Some(("vote", matches)) => handle_vote(matches)?,
+        Some(("progress", matches)) => handle_progress(matches)?,
+        Some(("read", matches)) => handle_read(matches)?,
+        Some(("dashboard", matches)) => handle_dashboard(matches)?,
+        Some(("search", matches)) => handle_search(matches)?,
+        Some(("novel", matches)) => handle_novel(matches)?,
+        Some(("config", matches)) => handle_config(matches)?,
+        Some(("daemon", matches)) => handle_daemon(matches)?,
+        Some(("watch", matches)) => handle_watch(matches)?,
+        Some(("feed", matches)) => handle_feed(matches)?,
+        Some(("serve", matches)) => handle_serve(matches)?,
+        Some(("opds", matches)) => handle_opds(matches)?,
+        Some(("library", matches)) => handle_library(matches)?,
+        Some(("album", matches)) => handle_album(matches)?,
+        Some(("author", matches)) => handle_author(matches)?,
         _ => unreachable!(),
     };
 
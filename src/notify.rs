@@ -0,0 +1,227 @@
+//! 书架更新的通知方式
+
+use crate::NovelUpdate;
+
+use anyhow::{bail, Result};
+use reqwest::{blocking::Client, header::CONTENT_TYPE};
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+pub trait Sink {
+    /// 发送一条更新通知
+    fn notify(&mut self, update: &NovelUpdate) -> Result<()>;
+}
+
+/// 通知的标题和正文
+pub fn summary_of(update: &NovelUpdate) -> (String, String) {
+    let title = format!("《{}》更新了", update.novel_name);
+    let body = if update.new_chapters.is_empty() {
+        format!("字数变化 {:+}", update.added_chars)
+    } else {
+        let titles: Vec<&str> = update
+            .new_chapters
+            .iter()
+            .map(|e| e.title.as_str())
+            .collect();
+        format!(
+            "新增 {} 章（{:+} 字）：{}",
+            update.new_chapters.len(),
+            update.added_chars,
+            titles.join("、")
+        )
+    };
+    (title, body)
+}
+
+/// 输出到标准输出，`json` 为真时每条通知输出一行 JSON
+pub struct StdoutSink {
+    pub json: bool,
+}
+
+impl Sink for StdoutSink {
+    fn notify(&mut self, update: &NovelUpdate) -> Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string(update)?);
+        } else {
+            let (title, body) = summary_of(update);
+            println!("{title}: {body}");
+        }
+        Ok(())
+    }
+}
+
+/// 通过 shell 执行本地命令，如 `notify-send "$SFUTILS_TITLE" "$SFUTILS_BODY"`
+///
+/// 命令的标准输入为更新的 JSON，环境变量 `SFUTILS_NOVEL_ID`、`SFUTILS_NOVEL_NAME`、
+/// `SFUTILS_NEW_CHAPTERS`、`SFUTILS_TITLE` 和 `SFUTILS_BODY` 给出更新的概要
+pub struct CommandSink {
+    pub command: String,
+}
+
+impl Sink for CommandSink {
+    fn notify(&mut self, update: &NovelUpdate) -> Result<()> {
+        let (shell, flag) = if cfg!(windows) {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        let (title, body) = summary_of(update);
+        let mut child = Command::new(shell)
+            .arg(flag)
+            .arg(&self.command)
+            .env("SFUTILS_NOVEL_ID", update.novel_id.to_string())
+            .env("SFUTILS_NOVEL_NAME", &update.novel_name)
+            .env(
+                "SFUTILS_NEW_CHAPTERS",
+                update.new_chapters.len().to_string(),
+            )
+            .env("SFUTILS_TITLE", title)
+            .env("SFUTILS_BODY", body)
+            .stdin(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // 命令可能不读取标准输入，忽略管道关闭的错误
+            let _ = stdin.write_all(serde_json::to_string(update)?.as_bytes());
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("command `{}` exited with {status}", self.command);
+        }
+        Ok(())
+    }
+}
+
+/// 以 JSON 请求体 POST 到指定的 URL
+pub struct WebhookSink {
+    url: String,
+    client: Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Client::new(),
+        }
+    }
+}
+
+impl Sink for WebhookSink {
+    fn notify(&mut self, update: &NovelUpdate) -> Result<()> {
+        let resp = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_string(update)?)
+            .send()?;
+        if !resp.status().is_success() {
+            bail!("webhook {} responded with {}", self.url, resp.status());
+        }
+        Ok(())
+    }
+}
+
+/// 每条通知以一行 JSON 追加到文件中
+pub struct JsonlSink {
+    pub path: PathBuf,
+}
+
+impl Sink for JsonlSink {
+    fn notify(&mut self, update: &NovelUpdate) -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(update)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NewChapter;
+
+    use std::thread;
+
+    fn update() -> NovelUpdate {
+        NovelUpdate {
+            novel_id: 1,
+            novel_name: "小说".to_string(),
+            author: "作者".to_string(),
+            last_update_time: 1684540800,
+            total_chars: 3000,
+            added_chars: 1000,
+            new_chapters: vec![NewChapter {
+                id: 2,
+                title: "第二章".to_string(),
+                volume: "第一卷".to_string(),
+                is_free: true,
+                creation_time: 1684540800,
+            }],
+        }
+    }
+
+    /// 在本地端口接收一个请求，以 `status` 响应并返回请求的方法、Content-Type 与请求体
+    fn listen(status: u16) -> (String, thread::JoinHandle<(String, String, String)>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", server.server_addr().to_ip().unwrap());
+        let handle = thread::spawn(move || {
+            let mut request = server.recv().unwrap();
+            let content_type = request
+                .headers()
+                .iter()
+                .find(|e| e.field.equiv("Content-Type"))
+                .map(|e| e.value.to_string())
+                .unwrap_or_default();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let method = request.method().to_string();
+            request.respond(tiny_http::Response::empty(status)).unwrap();
+            (method, content_type, body)
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn webhook_posts_update_as_json() {
+        let (url, handle) = listen(200);
+        WebhookSink::new(&url).notify(&update()).unwrap();
+
+        let (method, content_type, body) = handle.join().unwrap();
+        assert_eq!(method, "POST");
+        assert_eq!(content_type, "application/json");
+        assert_eq!(body, serde_json::to_string(&update()).unwrap());
+    }
+
+    #[test]
+    fn webhook_fails_on_error_status() {
+        let (url, handle) = listen(500);
+        assert!(WebhookSink::new(&url).notify(&update()).is_err());
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn jsonl_appends_one_line_per_update() {
+        let path = std::env::temp_dir().join(format!("sfutils-jsonl-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut sink = JsonlSink { path: path.clone() };
+        sink.notify(&update()).unwrap();
+        sink.notify(&update()).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["novel_id"], 1);
+            assert_eq!(value["new_chapters"][0]["title"], "第二章");
+        }
+    }
+}
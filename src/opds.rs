@@ -0,0 +1,312 @@
+//! 已下载小说的 OPDS 1.2 目录
+
+use crate::{
+    feed::{escape, rfc3339, server_now},
+    local::{BookStore, Library, LocalBook},
+    Id,
+};
+
+use anyhow::Result;
+use std::{collections::BTreeMap, fmt::Write};
+
+const NAVIGATION: &str = "application/atom+xml;profile=opds-catalog;kind=navigation";
+const ACQUISITION: &str = "application/atom+xml;profile=opds-catalog;kind=acquisition";
+
+/// 最近更新中列出的小说数量
+const RECENT: usize = 20;
+
+/// 百分号编码路径中的一段
+pub fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = text
+            .get(i + 1..i + 3)
+            .and_then(|e| u8::from_str_radix(e, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// 导航条目：标题、链接、说明与链接的目录类型
+struct Navigation {
+    title: String,
+    href: String,
+    content: String,
+    kind: &'static str,
+}
+
+pub struct OpdsCatalog<'a> {
+    store: &'a BookStore,
+    books: Vec<LocalBook>,
+}
+
+impl<'a> OpdsCatalog<'a> {
+    /// 读取书库中的小说建立目录，小说按最后更新时间从新到旧排列
+    pub fn new(store: &'a BookStore, library: &Library) -> Result<Self> {
+        Ok(Self {
+            store,
+            books: library.books()?,
+        })
+    }
+
+    fn updated(&self) -> String {
+        let time = self
+            .books
+            .first()
+            .map_or(server_now(), |e| e.last_update_time);
+        rfc3339(time)
+    }
+
+    fn header(&self, xml: &mut String, path: &str, title: &str, kind: &str) -> Result<()> {
+        writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+        writeln!(
+            xml,
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/terms/" xmlns:opds="http://opds-spec.org/2010/catalog">"#
+        )?;
+        writeln!(xml, "  <id>urn:sfutils:opds:{}</id>", escape(path))?;
+        writeln!(xml, "  <title>{}</title>", escape(title))?;
+        writeln!(xml, "  <updated>{}</updated>", self.updated())?;
+        writeln!(
+            xml,
+            r#"  <link rel="self" href="/opds{}" type="{kind}"/>"#,
+            escape(path)
+        )?;
+        writeln!(
+            xml,
+            r#"  <link rel="start" href="/opds" type="{NAVIGATION}"/>"#
+        )?;
+        if !path.is_empty() {
+            writeln!(
+                xml,
+                r#"  <link rel="up" href="/opds" type="{NAVIGATION}"/>"#
+            )?;
+        }
+        Ok(())
+    }
+
+    fn navigation_feed(&self, path: &str, title: &str, entries: &[Navigation]) -> Result<String> {
+        let mut xml = String::new();
+        self.header(&mut xml, path, title, NAVIGATION)?;
+        for entry in entries {
+            writeln!(xml, "  <entry>")?;
+            writeln!(xml, "    <title>{}</title>", escape(&entry.title))?;
+            writeln!(xml, "    <id>urn:sfutils:opds:{}</id>", escape(&entry.href))?;
+            writeln!(xml, "    <updated>{}</updated>", self.updated())?;
+            writeln!(
+                xml,
+                r#"    <content type="text">{}</content>"#,
+                escape(&entry.content)
+            )?;
+            writeln!(
+                xml,
+                r#"    <link rel="subsection" href="/opds{}" type="{}"/>"#,
+                escape(&entry.href),
+                entry.kind
+            )?;
+            writeln!(xml, "  </entry>")?;
+        }
+        writeln!(xml, "</feed>")?;
+        Ok(xml)
+    }
+
+    fn acquisition_feed(&self, path: &str, title: &str, books: &[&LocalBook]) -> Result<String> {
+        let mut xml = String::new();
+        self.header(&mut xml, path, title, ACQUISITION)?;
+        for book in books {
+            writeln!(xml, "  <entry>")?;
+            writeln!(xml, "    <title>{}</title>", escape(&book.name))?;
+            writeln!(xml, "    <id>urn:sfacg:novel:{}</id>", book.id)?;
+            writeln!(
+                xml,
+                "    <updated>{}</updated>",
+                rfc3339(book.last_update_time)
+            )?;
+            writeln!(
+                xml,
+                "    <author><name>{}</name><uri>/opds/authors/{}</uri></author>",
+                escape(&book.author),
+                book.author_id
+            )?;
+            writeln!(xml, "    <dc:language>zh-CN</dc:language>")?;
+            for tag in &book.tags {
+                writeln!(
+                    xml,
+                    r#"    <category term="{0}" label="{0}"/>"#,
+                    escape(tag)
+                )?;
+            }
+            writeln!(
+                xml,
+                r#"    <summary type="text">{}</summary>"#,
+                escape(&book.intro)
+            )?;
+            if self.store.has_cover(book.id) {
+                for rel in ["image", "image/thumbnail"] {
+                    writeln!(
+                        xml,
+                        r#"    <link rel="http://opds-spec.org/{rel}" href="/books/{}/cover.jpg" type="image/jpeg"/>"#,
+                        book.id
+                    )?;
+                }
+            }
+            writeln!(
+                xml,
+                r#"    <link rel="http://opds-spec.org/acquisition" href="/books/{}.epub" type="application/epub+zip"/>"#,
+                book.id
+            )?;
+            writeln!(
+                xml,
+                r#"    <link rel="http://opds-spec.org/acquisition" href="/books/{}.txt" type="text/plain"/>"#,
+                book.id
+            )?;
+            writeln!(xml, "  </entry>")?;
+        }
+        writeln!(xml, "</feed>")?;
+        Ok(xml)
+    }
+
+    /// 按作者分组，键为作者 ID
+    fn authors(&self) -> BTreeMap<Id, Vec<&LocalBook>> {
+        let mut authors: BTreeMap<Id, Vec<&LocalBook>> = BTreeMap::new();
+        for book in &self.books {
+            authors.entry(book.author_id).or_default().push(book);
+        }
+        authors
+    }
+
+    fn tags(&self) -> BTreeMap<&str, Vec<&LocalBook>> {
+        let mut tags: BTreeMap<&str, Vec<&LocalBook>> = BTreeMap::new();
+        for book in &self.books {
+            for tag in &book.tags {
+                tags.entry(tag.as_str()).or_default().push(book);
+            }
+        }
+        tags
+    }
+
+    /// 获取 `/opds` 下路径对应的目录，路径不存在时返回 `None`
+    pub fn feed(&self, path: &str) -> Result<Option<String>> {
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|e| !e.is_empty())
+            .map(percent_decode)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let all: Vec<&LocalBook> = self.books.iter().collect();
+        let filter = |finished: bool| -> Vec<&LocalBook> {
+            self.books
+                .iter()
+                .filter(|e| e.is_finished == finished)
+                .collect()
+        };
+
+        let feed = match segments.as_slice() {
+            [] => {
+                let count = |n: usize| format!("{n} 本小说");
+                let authors = format!("{} 位作者", self.authors().len());
+                let tags = format!("{} 个标签", self.tags().len());
+                let entries = [
+                    ("按作者", "/authors", authors, NAVIGATION),
+                    ("按标签", "/tags", tags, NAVIGATION),
+                    (
+                        "已完结",
+                        "/finished",
+                        count(filter(true).len()),
+                        ACQUISITION,
+                    ),
+                    (
+                        "连载中",
+                        "/ongoing",
+                        count(filter(false).len()),
+                        ACQUISITION,
+                    ),
+                    (
+                        "最近更新",
+                        "/recent",
+                        count(all.len().min(RECENT)),
+                        ACQUISITION,
+                    ),
+                    ("全部", "/all", count(all.len()), ACQUISITION),
+                ]
+                .map(|(title, href, content, kind)| Navigation {
+                    title: title.to_string(),
+                    href: href.to_string(),
+                    content,
+                    kind,
+                });
+                self.navigation_feed("", "SF 轻小说书库", &entries)
+            }
+            ["authors"] => {
+                let entries: Vec<Navigation> = self
+                    .authors()
+                    .into_iter()
+                    .map(|(id, books)| Navigation {
+                        title: books[0].author.to_owned(),
+                        href: format!("/authors/{id}"),
+                        content: format!("{} 本小说", books.len()),
+                        kind: ACQUISITION,
+                    })
+                    .collect();
+                self.navigation_feed("/authors", "按作者", &entries)
+            }
+            ["authors", id] => {
+                let Some(books) = id.parse().ok().and_then(|e| self.authors().remove(&e)) else {
+                    return Ok(None);
+                };
+                let title = books[0].author.to_owned();
+                self.acquisition_feed(&format!("/authors/{id}"), &title, &books)
+            }
+            ["tags"] => {
+                let entries: Vec<Navigation> = self
+                    .tags()
+                    .into_iter()
+                    .map(|(tag, books)| Navigation {
+                        title: tag.to_string(),
+                        href: format!("/tags/{}", percent_encode(tag)),
+                        content: format!("{} 本小说", books.len()),
+                        kind: ACQUISITION,
+                    })
+                    .collect();
+                self.navigation_feed("/tags", "按标签", &entries)
+            }
+            ["tags", tag] => {
+                let Some(books) = self.tags().remove(tag) else {
+                    return Ok(None);
+                };
+                self.acquisition_feed(&format!("/tags/{}", percent_encode(tag)), tag, &books)
+            }
+            ["finished"] => self.acquisition_feed("/finished", "已完结", &filter(true)),
+            ["ongoing"] => self.acquisition_feed("/ongoing", "连载中", &filter(false)),
+            ["recent"] => {
+                self.acquisition_feed("/recent", "最近更新", &all[..all.len().min(RECENT)])
+            }
+            ["all"] => self.acquisition_feed("/all", "全部", &all),
+            _ => return Ok(None),
+        };
+        Ok(Some(feed?))
+    }
+}